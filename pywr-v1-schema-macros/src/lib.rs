@@ -10,7 +10,7 @@ pub fn pywr_node_macro(input: TokenStream) -> TokenStream {
 }
 
 /// A derive macro for Pywr parameters that implements `parameters`, `parameters_mut`,
-/// `resource_paths` and `update_resource_paths` methods.
+/// `resource_paths`, `update_resource_paths` and `table_references` methods.
 #[proc_macro_derive(PywrParameter)]
 pub fn pywr_parameter_macro(input: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree
@@ -18,16 +18,38 @@ pub fn pywr_parameter_macro(input: TokenStream) -> TokenStream {
 
     let mut expanded = impl_parameter_references_derive(&input);
     expanded.extend(impl_parameter_resource_paths_derive(&input));
+    expanded.extend(impl_table_references_derive(&input));
 
     expanded
 }
 
+/// A derive macro for Pywr recorders that implements `parameters` and `parameters_mut` methods.
+///
+/// Recorders' file paths are outputs rather than external resources, so no resource path
+/// methods are generated.
+#[proc_macro_derive(PywrRecorder)]
+pub fn pywr_recorder_macro(input: TokenStream) -> TokenStream {
+    // Parse the input tokens into a syntax tree
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    impl_parameter_references_derive(&input)
+}
+
+/// A derive macro for internally tagged enums that implements a `TYPE_ALIASES` constant
+/// containing the tag of each variant and its `#[serde(alias = "...")]` names, and a
+/// `deserialize_variant` method that deserializes a variant from its tag.
+#[proc_macro_derive(PywrTaggedEnum, attributes(serde))]
+pub fn pywr_tagged_enum_macro(input: TokenStream) -> TokenStream {
+    // Parse the input tokens into a syntax tree
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    impl_tagged_enum_derive(&input)
+}
+
 /// Generates a [`TokenStream`] containing the implementation of two methods, `parameters`
 /// and `parameters_mut`, for the given struct.
 ///
 /// The `parameters` method returns a [`HashMap`] of parameter names to [`ParameterValueType`],
 /// and the `parameters_mut` method returns a [`HashMap`] of parameter names to [`ParameterValueTypeMut`].
-/// This is intended to be used for nodes and parameter structs in the Pywr schema.
+/// This is intended to be used for node, parameter and recorder structs in the Pywr schema.
 ///
 /// Currently the implementation is limited to simple type definitions such as `Option<ParameterValue>` or `ParameterValue`.
 fn impl_parameter_references_derive(ast: &syn::DeriveInput) -> TokenStream {
@@ -137,7 +159,9 @@ fn impl_parameter_references_derive(ast: &syn::DeriveInput) -> TokenStream {
         // Hand the output tokens back to the compiler.
         TokenStream::from(expanded)
     } else {
-        panic!("Only structs are supported for #[derive(PywrNode)] or #[derive(PywrParameter)]")
+        panic!(
+            "Only structs are supported for #[derive(PywrNode)], #[derive(PywrParameter)] or #[derive(PywrRecorder)]"
+        )
     }
 }
 
@@ -288,7 +312,76 @@ fn impl_parameter_resource_paths_derive(ast: &syn::DeriveInput) -> TokenStream {
         // Hand the output tokens back to the compiler.
         TokenStream::from(expanded)
     } else {
-        panic!("Only structs are supported for #[derive(PywrNode)] or #[derive(PywrParameter)]")
+        panic!(
+            "Only structs are supported for #[derive(PywrNode)], #[derive(PywrParameter)] or #[derive(PywrRecorder)]"
+        )
+    }
+}
+
+/// Generates a [`TokenStream`] containing the implementation of a `table_references` method.
+///
+/// The method returns a [`HashMap`] of attribute names to the names of the tables referenced
+/// by any `TableDataRef` fields of the struct.
+fn impl_table_references_derive(ast: &syn::DeriveInput) -> TokenStream {
+    // Name of the node type
+    let name = &ast.ident;
+
+    if let syn::Data::Struct(data) = &ast.data {
+        // Identify the `TableDataRef` fields and whether they are optional.
+        let table_fields: Vec<(syn::Ident, bool)> = data
+            .fields
+            .iter()
+            .filter_map(|field| {
+                let field_ident = field.ident.as_ref()?;
+
+                match type_to_ident(&field.ty) {
+                    Some(PywrField::Optional(ident)) => {
+                        (ident == "TableDataRef").then_some((field_ident.clone(), true))
+                    }
+                    Some(PywrField::Required(ident)) => {
+                        (ident == "TableDataRef").then_some((field_ident.clone(), false))
+                    }
+                    None => None, // All other field types are ignored
+                }
+            })
+            .collect();
+
+        let inserts = table_fields
+            .iter()
+            .map(|(ident, optional)| {
+                let key = ident.to_string();
+                if *optional {
+                    quote! {
+                        if let Some(t) = &self.#ident {
+                            references.insert(#key, t.table.as_str());
+                        }
+                    }
+                } else {
+                    quote! {
+                        references.insert(#key, self.#ident.table.as_str());
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let expanded = quote! {
+            impl #name {
+                pub fn table_references(&self) -> HashMap<&str, &str> {
+                    let mut references = HashMap::new();
+                    #(
+                        #inserts
+                    )*
+                    references
+                }
+            }
+        };
+
+        // Hand the output tokens back to the compiler.
+        TokenStream::from(expanded)
+    } else {
+        panic!(
+            "Only structs are supported for #[derive(PywrNode)], #[derive(PywrParameter)] or #[derive(PywrRecorder)]"
+        )
     }
 }
 
@@ -297,6 +390,73 @@ enum PywrField {
     Required(syn::Ident),
 }
 
+/// Generates a [`TokenStream`] containing a `TYPE_ALIASES` constant and a
+/// `deserialize_variant` method for the given enum. Each variant must contain a single
+/// unnamed field.
+fn impl_tagged_enum_derive(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+
+    let variants = match &ast.data {
+        syn::Data::Enum(data) => &data.variants,
+        _ => panic!("Only enums are supported"),
+    };
+
+    let mut entries = Vec::with_capacity(variants.len());
+    let mut arms = Vec::with_capacity(variants.len());
+    for variant in variants {
+        let mut tag = variant.ident.to_string();
+        let mut aliases = Vec::new();
+
+        for attr in variant.attrs.iter().filter(|a| a.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("alias") {
+                    aliases.push(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if meta.path.is_ident("rename") {
+                    tag = meta.value()?.parse::<syn::LitStr>()?.value();
+                } else if meta.input.peek(syn::Token![=]) {
+                    // Skip the value of any other attribute.
+                    meta.value()?.parse::<syn::Expr>()?;
+                }
+                Ok(())
+            })
+            .expect("Failed to parse serde attribute");
+        }
+
+        let ident = &variant.ident;
+        let ty = match &variant.fields {
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => panic!("Only variants with a single unnamed field are supported"),
+        };
+
+        arms.push(quote! {
+            #tag #(| #aliases)* => Some(serde_path_to_error::deserialize::<_, #ty>(value).map(Self::#ident))
+        });
+        entries.push(quote! { (#tag, &[#(#aliases),*]) });
+    }
+
+    let expanded = quote! {
+        impl #name {
+            /// The tag of each variant and the aliases accepted when deserializing it.
+            pub const TYPE_ALIASES: &'static [(&'static str, &'static [&'static str])] = &[#(#entries),*];
+
+            /// Deserialize the variant with the tag (or alias) `ty` from `value`, which should
+            /// not contain the tag itself. The error includes the path of the attribute that
+            /// failed to deserialize. Returns `None` if `ty` is not the tag of a variant.
+            pub(crate) fn deserialize_variant(
+                ty: &str,
+                value: serde_json::Value,
+            ) -> Option<Result<Self, serde_path_to_error::Error<serde_json::Error>>> {
+                match ty {
+                    #(#arms,)*
+                    _ => None,
+                }
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
 /// Returns the last segment of a type path as an identifier
 fn type_to_ident(ty: &syn::Type) -> Option<PywrField> {
     match ty {
@@ -359,7 +519,9 @@ fn type_to_ident(ty: &syn::Type) -> Option<PywrField> {
 }
 
 fn is_parameter_ident(ident: &syn::Ident) -> bool {
-    (ident == "ParameterValue") || (ident == "ParameterValues")
+    (ident == "ParameterValue")
+        || (ident == "ParameterValues")
+        || (ident == "OptionalParameterValues")
 }
 
 enum PathFieldType {
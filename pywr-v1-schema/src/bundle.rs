@@ -0,0 +1,328 @@
+use crate::PywrSchemaError;
+use crate::model::PywrModel;
+use crate::resources::Resource;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The directory of a bundle that its resources are copied into.
+const RESOURCE_DIR: &str = "data";
+
+/// A local resource of a model, and where it is copied to in a [`Bundle`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundledResource {
+    /// The path of the resource on disk.
+    pub source: PathBuf,
+    /// The path of the resource relative to the root of the bundle.
+    pub path: PathBuf,
+}
+
+/// A model and the local resources it references, which can be written to a directory or tar
+/// archive that loads without the original files.
+#[derive(Clone)]
+pub struct Bundle {
+    /// The model, with its resource paths rewritten to those of the resources in the bundle.
+    pub model: PywrModel,
+    /// The file name of the model in the bundle.
+    pub model_name: PathBuf,
+    /// The resources to copy into the bundle, in order of their path in the bundle.
+    pub resources: Vec<BundledResource>,
+}
+
+impl PywrModel {
+    /// Collect this model, which was loaded from `path`, and its local resources into a
+    /// [`Bundle`].
+    ///
+    /// Each local resource is given a unique name in the bundle's `data` directory, with a
+    /// numeric suffix added if several resources have the same file name. Resources that
+    /// resolve to the same file share a single copy. Remote resources are left unchanged.
+    ///
+    /// JSON includes are merged into the bundled model, as with
+    /// [`PywrNetwork::load_includes`](crate::model::PywrNetwork::load_includes), so that the
+    /// resources they reference are collected. Other includes (e.g. Python files) are
+    /// bundled as resources.
+    ///
+    /// Any error from merging the includes is returned. A
+    /// [`PywrSchemaError::LocalResourceNotFound`] or [`PywrSchemaError::InvalidUrlFound`]
+    /// error is returned for the first (by path) resource that is missing or invalid.
+    pub fn bundle<P: AsRef<Path>>(&self, path: P) -> Result<Bundle, PywrSchemaError> {
+        let path = path.as_ref();
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let model_name = PathBuf::from(path.file_name().unwrap_or("model.json".as_ref()));
+
+        let mut model = self.clone();
+        model.network.load_includes(path)?;
+
+        let mut paths: Vec<_> = model.resource_paths().into_iter().collect();
+        paths.sort();
+
+        // Group the resource paths by the local file they resolve to.
+        let mut sources: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+        for p in paths {
+            if let Resource::Local(source) = Resource::new(&p, dir)? {
+                if !source.is_file() {
+                    return Err(PywrSchemaError::LocalResourceNotFound(source));
+                }
+                sources.entry(source).or_default().push(p);
+            }
+        }
+
+        let mut names = HashSet::new();
+        let mut new_paths = HashMap::new();
+        let mut resources = Vec::new();
+        for (source, paths) in sources {
+            let name = unique_name(&source, &mut names);
+            // Always use `/` so that the model loads on any platform.
+            let bundled = PathBuf::from(format!("{RESOURCE_DIR}/{name}"));
+
+            for p in paths {
+                new_paths.insert(p, bundled.clone());
+            }
+            resources.push(BundledResource {
+                source,
+                path: bundled,
+            });
+        }
+        resources.sort_by(|a, b| a.path.cmp(&b.path));
+
+        model.update_resource_paths(&new_paths);
+
+        Ok(Bundle {
+            model,
+            model_name,
+            resources,
+        })
+    }
+}
+
+impl Bundle {
+    /// Write the bundle into the directory `dir`, which is created if it does not exist.
+    ///
+    /// Existing files with the same names as those in the bundle are overwritten.
+    pub fn write_dir<P: AsRef<Path>>(&self, dir: P) -> Result<(), PywrSchemaError> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir.join(RESOURCE_DIR))?;
+
+        for resource in &self.resources {
+            fs::copy(&resource.source, dir.join(&resource.path))?;
+        }
+        fs::write(dir.join(&self.model_name), self.model_json()?)?;
+
+        Ok(())
+    }
+
+    /// Write the bundle as a tar archive to `writer`.
+    #[cfg(feature = "bundle-tar")]
+    pub fn write_tar<W: std::io::Write>(&self, writer: W) -> Result<(), PywrSchemaError> {
+        let mut builder = tar::Builder::new(writer);
+
+        let json = self.model_json()?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(json.len() as u64);
+        header.set_mode(0o644);
+        builder.append_data(&mut header, &self.model_name, json.as_bytes())?;
+
+        for resource in &self.resources {
+            builder.append_path_with_name(&resource.source, &resource.path)?;
+        }
+
+        builder.into_inner()?.flush()?;
+        Ok(())
+    }
+
+    /// The JSON of the bundled model.
+    fn model_json(&self) -> Result<String, PywrSchemaError> {
+        Ok(serde_json::to_string_pretty(&self.model)?)
+    }
+}
+
+/// Return the file name of `source`, with a numeric suffix if it is already in `names`, and
+/// add it to `names`.
+fn unique_name(source: &Path, names: &mut HashSet<String>) -> String {
+    let name = source
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut unique = name.clone();
+    let mut i = 1;
+    while names.contains(&unique) {
+        unique = match (source.file_stem(), source.extension()) {
+            (Some(stem), Some(ext)) => {
+                format!("{}_{i}.{}", stem.to_string_lossy(), ext.to_string_lossy())
+            }
+            _ => format!("{name}_{i}"),
+        };
+        i += 1;
+    }
+
+    names.insert(unique.clone());
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PywrModel, PywrSchemaError};
+    use std::collections::HashSet;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    /// Write a model with resources in two directories that share a file name, and return
+    /// the path of the model.
+    fn write_model(dir: &Path) -> PathBuf {
+        fs::create_dir_all(dir.join("a")).unwrap();
+        fs::create_dir_all(dir.join("b")).unwrap();
+        fs::write(dir.join("a/flow.csv"), "a\n1\n").unwrap();
+        fs::write(dir.join("b/flow.csv"), "b\n2\n").unwrap();
+
+        let model = r#"
+        {
+            "metadata": {"title": "Bundle"},
+            "timestepper": {"start": "2015-01-01", "end": "2015-12-31", "timestep": 1},
+            "nodes": [
+                {
+                    "name": "supply1",
+                    "type": "Input",
+                    "max_flow": {
+                        "type": "max",
+                        "parameter": {"type": "dataframe", "url": "./a/flow.csv", "column": "a"}
+                    }
+                }
+            ],
+            "tables": {
+                "a": {"url": "a/flow.csv"},
+                "b": {"url": "b/flow.csv"},
+                "remote": {"url": "https://example.com/flow.csv"}
+            }
+        }
+        "#;
+        let path = dir.join("model.json");
+        fs::write(&path, model).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_bundle_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let path = write_model(&dir.join("src"));
+
+        let model = PywrModel::from_path(&path).unwrap();
+        let bundle = model.bundle(&path).unwrap();
+
+        let bundled: Vec<_> = bundle.resources.iter().map(|r| r.path.clone()).collect();
+        assert_eq!(
+            bundled,
+            vec![
+                PathBuf::from("data/flow.csv"),
+                PathBuf::from("data/flow_1.csv")
+            ]
+        );
+
+        let out = dir.join("out");
+        bundle.write_dir(&out).unwrap();
+        assert_eq!(
+            fs::read_to_string(out.join("data/flow.csv")).unwrap(),
+            "a\n1\n"
+        );
+        assert_eq!(
+            fs::read_to_string(out.join("data/flow_1.csv")).unwrap(),
+            "b\n2\n"
+        );
+
+        let loaded = PywrModel::from_path(out.join("model.json")).unwrap();
+        assert_eq!(
+            loaded.resource_paths(),
+            HashSet::from([
+                PathBuf::from("data/flow.csv"),
+                PathBuf::from("data/flow_1.csv"),
+                PathBuf::from("https://example.com/flow.csv"),
+            ])
+        );
+        assert!(loaded.check_resources(out.join("model.json")).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "bundle-tar")]
+    fn test_bundle_tar() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_model(dir.path());
+
+        let model = PywrModel::from_path(&path).unwrap();
+        let mut archive = Vec::new();
+        model
+            .bundle(&path)
+            .unwrap()
+            .write_tar(&mut archive)
+            .unwrap();
+
+        let mut names: Vec<_> = tar::Archive::new(archive.as_slice())
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().to_path_buf())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                PathBuf::from("data/flow.csv"),
+                PathBuf::from("data/flow_1.csv"),
+                PathBuf::from("model.json")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bundle_includes() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let path = write_model(&dir.join("src"));
+
+        // Include a network that references another resource, and a Python file.
+        let model = fs::read_to_string(&path).unwrap().replacen(
+            "{",
+            r#"{"includes": ["network.json", "my_parameter.py"],"#,
+            1,
+        );
+        fs::write(&path, model).unwrap();
+        fs::write(
+            dir.join("src/network.json"),
+            r#"{"tables": {"c": {"url": "c/flow.csv"}}}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(dir.join("src/c")).unwrap();
+        fs::write(dir.join("src/c/flow.csv"), "c\n3\n").unwrap();
+        fs::write(dir.join("src/my_parameter.py"), "").unwrap();
+
+        let model = PywrModel::from_path(&path).unwrap();
+        let bundle = model.bundle(&path).unwrap();
+        assert_eq!(bundle.resources.len(), 4);
+
+        let out = dir.join("out");
+        bundle.write_dir(&out).unwrap();
+        let loaded = PywrModel::from_path(out.join("model.json")).unwrap();
+        assert_eq!(
+            loaded.network.includes,
+            Some(vec![PathBuf::from("data/my_parameter.py")])
+        );
+        assert!(
+            loaded
+                .network
+                .tables
+                .as_ref()
+                .is_some_and(|tables| tables.iter().any(|t| t.name == "c"))
+        );
+        assert!(loaded.check_resources(out.join("model.json")).is_empty());
+    }
+
+    #[test]
+    fn test_bundle_missing_resource() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/models/timeseries1.json");
+        let model = PywrModel::from_path(&path).unwrap();
+
+        assert!(matches!(
+            model.bundle(&path),
+            Err(PywrSchemaError::LocalResourceNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,453 @@
+use crate::json_path::{JsonPath, PathSegment};
+use crate::model::PywrModel;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+/// A component of a model that is compared by [`PywrModel::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffComponent {
+    Metadata,
+    Timestepper,
+    Scenario(String),
+    ScenarioCombinations,
+    Node(String),
+    Edge { from_node: String, to_node: String },
+    Parameter(String),
+    Table(String),
+    Recorder(String),
+}
+
+impl DiffComponent {
+    /// The name used to prefix the component's attributes.
+    fn prefix(&self) -> String {
+        match self {
+            Self::Metadata => "metadata".to_string(),
+            Self::Timestepper => "timestepper".to_string(),
+            Self::Edge { from_node, to_node } => format!("{from_node} -> {to_node}"),
+            Self::ScenarioCombinations => "scenario_combinations".to_string(),
+            Self::Scenario(name)
+            | Self::Node(name)
+            | Self::Parameter(name)
+            | Self::Table(name)
+            | Self::Recorder(name) => name.clone(),
+        }
+    }
+}
+
+impl fmt::Display for DiffComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Metadata => write!(f, "metadata"),
+            Self::Timestepper => write!(f, "timestepper"),
+            Self::Scenario(name) => write!(f, "scenario `{name}`"),
+            Self::ScenarioCombinations => write!(f, "scenario combinations"),
+            Self::Node(name) => write!(f, "node `{name}`"),
+            Self::Edge { from_node, to_node } => write!(f, "edge `{from_node}` -> `{to_node}`"),
+            Self::Parameter(name) => write!(f, "parameter `{name}`"),
+            Self::Table(name) => write!(f, "table `{name}`"),
+            Self::Recorder(name) => write!(f, "recorder `{name}`"),
+        }
+    }
+}
+
+/// A change to a single attribute of a component.
+///
+/// The attribute is given as a path within the component's JSON representation (e.g.
+/// `max_flow` or `max_flow.parameters[1]`), which is empty if the whole component changed
+/// (e.g. the scenario combinations). `old` or `new` is `None` if the attribute was added or
+/// removed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeChange {
+    pub attribute: JsonPath,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+impl AttributeChange {
+    /// Write the change as if its attribute was at `path`.
+    fn write_at(&self, f: &mut fmt::Formatter<'_>, path: &JsonPath) -> fmt::Result {
+        let display = |value: &Option<Value>| match value {
+            Some(value) => value.to_string(),
+            None => "(none)".to_string(),
+        };
+
+        write!(
+            f,
+            "{path}: {} -> {}",
+            display(&self.old),
+            display(&self.new)
+        )
+    }
+}
+
+impl fmt::Display for AttributeChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_at(f, &self.attribute)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed(Vec<AttributeChange>),
+}
+
+/// A difference between two models.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub component: DiffComponent,
+    pub kind: ChangeKind,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ChangeKind::Added => write!(f, "+ {}", self.component),
+            ChangeKind::Removed => write!(f, "- {}", self.component),
+            ChangeKind::Changed(attributes) => {
+                write!(f, "~ {}", self.component)?;
+                let prefix = PathSegment::Key(self.component.prefix());
+                for attribute in attributes {
+                    // Show the path of the attribute from the name of the component.
+                    let path: JsonPath = std::iter::once(prefix.clone())
+                        .chain(attribute.attribute.iter().cloned())
+                        .collect();
+                    write!(f, "\n    ")?;
+                    attribute.write_at(f, &path)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl PywrModel {
+    /// Compare this model with `other`, returning the changes needed to turn this model
+    /// into `other`.
+    ///
+    /// Scenarios, nodes, parameters, tables and recorders are matched by name, so the order
+    /// in which they are defined does not matter. Edges are matched by the nodes and slots
+    /// they connect. Changed components report each attribute that differs, and scenario
+    /// combinations are compared as a whole.
+    ///
+    /// Changes are ordered by the type of component, and then by their position in this
+    /// model, followed by any components that were added in `other`.
+    pub fn diff(&self, other: &PywrModel) -> Vec<Change> {
+        let mut changes = Vec::new();
+
+        changes.extend(diff_component(
+            DiffComponent::Metadata,
+            &self.metadata,
+            &other.metadata,
+        ));
+        changes.extend(diff_component(
+            DiffComponent::Timestepper,
+            &self.timestepper,
+            &other.timestepper,
+        ));
+
+        changes.extend(diff_components(
+            self.scenarios.iter().flatten().map(|s| (s.name.clone(), s)),
+            other
+                .scenarios
+                .iter()
+                .flatten()
+                .map(|s| (s.name.clone(), s)),
+            DiffComponent::Scenario,
+        ));
+
+        changes.extend(diff_component(
+            DiffComponent::ScenarioCombinations,
+            &self.scenario_combinations,
+            &other.scenario_combinations,
+        ));
+
+        changes.extend(diff_components(
+            self.network
+                .nodes
+                .iter()
+                .flatten()
+                .map(|n| (n.name().to_string(), n)),
+            other
+                .network
+                .nodes
+                .iter()
+                .flatten()
+                .map(|n| (n.name().to_string(), n)),
+            DiffComponent::Node,
+        ));
+
+        // Edges do not have any attributes, so can only be added or removed.
+        let old_edges: HashSet<_> = self.network.edges.iter().flatten().map(edge_key).collect();
+        let new_edges: HashSet<_> = other.network.edges.iter().flatten().map(edge_key).collect();
+        let edge_changes = self
+            .network
+            .edges
+            .iter()
+            .flatten()
+            .filter(|e| !new_edges.contains(&edge_key(e)))
+            .map(|e| (e, ChangeKind::Removed))
+            .chain(
+                other
+                    .network
+                    .edges
+                    .iter()
+                    .flatten()
+                    .filter(|e| !old_edges.contains(&edge_key(e)))
+                    .map(|e| (e, ChangeKind::Added)),
+            );
+        changes.extend(edge_changes.map(|(edge, kind)| Change {
+            component: DiffComponent::Edge {
+                from_node: edge.from_node.clone(),
+                to_node: edge.to_node.clone(),
+            },
+            kind,
+        }));
+
+        changes.extend(diff_components(
+            self.network
+                .parameters
+                .iter()
+                .flat_map(|p| p.iter())
+                .map(|p| (p.name().unwrap_or_default().to_string(), p)),
+            other
+                .network
+                .parameters
+                .iter()
+                .flat_map(|p| p.iter())
+                .map(|p| (p.name().unwrap_or_default().to_string(), p)),
+            DiffComponent::Parameter,
+        ));
+
+        changes.extend(diff_components(
+            self.network
+                .tables
+                .iter()
+                .flat_map(|t| t.iter())
+                .map(|t| (t.name.clone(), t)),
+            other
+                .network
+                .tables
+                .iter()
+                .flat_map(|t| t.iter())
+                .map(|t| (t.name.clone(), t)),
+            DiffComponent::Table,
+        ));
+
+        changes.extend(diff_components(
+            self.network
+                .recorders
+                .iter()
+                .flat_map(|r| r.iter())
+                .map(|r| (r.name().to_string(), r)),
+            other
+                .network
+                .recorders
+                .iter()
+                .flat_map(|r| r.iter())
+                .map(|r| (r.name().to_string(), r)),
+            DiffComponent::Recorder,
+        ));
+
+        changes
+    }
+}
+
+/// Identify an edge by its serialised form, which includes any slots.
+fn edge_key(edge: &crate::edge::Edge) -> String {
+    serde_json::to_string(edge).expect("Edges can be serialised.")
+}
+
+/// Compare two lists of named components. Only the first component with a given name is
+/// compared; duplicate names are reported by [`crate::PywrNetwork::validate`].
+fn diff_components<'a, T, F>(
+    old: impl Iterator<Item = (String, &'a T)>,
+    new: impl Iterator<Item = (String, &'a T)>,
+    component: F,
+) -> Vec<Change>
+where
+    T: Serialize + PartialEq + 'a,
+    F: Fn(String) -> DiffComponent,
+{
+    let old: Vec<_> = old.collect();
+    let new: Vec<_> = new.collect();
+
+    let mut new_by_name = HashMap::new();
+    for (name, item) in &new {
+        new_by_name.entry(name.as_str()).or_insert(*item);
+    }
+
+    let mut changes = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (name, item) in &old {
+        if !seen.insert(name.as_str()) {
+            continue;
+        }
+
+        match new_by_name.get(name.as_str()) {
+            Some(other) => changes.extend(diff_component(component(name.clone()), *item, *other)),
+            None => changes.push(Change {
+                component: component(name.clone()),
+                kind: ChangeKind::Removed,
+            }),
+        }
+    }
+
+    for (name, _) in &new {
+        if seen.insert(name.as_str()) {
+            changes.push(Change {
+                component: component(name.clone()),
+                kind: ChangeKind::Added,
+            });
+        }
+    }
+
+    changes
+}
+
+/// Compare two versions of the same component, returning `None` if they are equal.
+fn diff_component<T: Serialize + PartialEq>(
+    component: DiffComponent,
+    old: &T,
+    new: &T,
+) -> Option<Change> {
+    if old == new {
+        return None;
+    }
+
+    let old = serde_json::to_value(old).expect("Schema types can be serialised.");
+    let new = serde_json::to_value(new).expect("Schema types can be serialised.");
+
+    // A component that is `None` (e.g. the scenario combinations) is reported as absent.
+    let mut attributes = Vec::new();
+    diff_values(
+        &mut JsonPath::new(),
+        Some(&old).filter(|v| !v.is_null()),
+        Some(&new).filter(|v| !v.is_null()),
+        &mut attributes,
+    );
+
+    (!attributes.is_empty()).then_some(Change {
+        component,
+        kind: ChangeKind::Changed(attributes),
+    })
+}
+
+/// Compare two JSON values at `path`, recursing into objects and arrays of the same length.
+fn diff_values(
+    path: &mut JsonPath,
+    old: Option<&Value>,
+    new: Option<&Value>,
+    changes: &mut Vec<AttributeChange>,
+) {
+    match (old, new) {
+        (Some(old), Some(new)) if old == new => {}
+        (Some(Value::Object(old)), Some(Value::Object(new))) => {
+            let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for key in keys {
+                path.push(PathSegment::Key(key.clone()));
+                diff_values(path, old.get(key), new.get(key), changes);
+                path.pop();
+            }
+        }
+        (Some(Value::Array(old)), Some(Value::Array(new))) if old.len() == new.len() => {
+            for (i, (old, new)) in old.iter().zip(new).enumerate() {
+                path.push(PathSegment::Index(i));
+                diff_values(path, Some(old), Some(new), changes);
+                path.pop();
+            }
+        }
+        (None, None) => {}
+        (old, new) => changes.push(AttributeChange {
+            attribute: path.clone(),
+            old: old.cloned(),
+            new: new.cloned(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PywrModel;
+    use crate::diff::{ChangeKind, DiffComponent};
+    use crate::json_path::{JsonPath, PathSegment};
+    use std::path::PathBuf;
+
+    fn load_test_model(name: &str) -> PywrModel {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("models")
+            .join(name);
+        PywrModel::from_path(path).unwrap()
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        let model = load_test_model("reservoir1.json");
+        assert!(model.diff(&model.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = load_test_model("simple1.json");
+        let mut data = serde_json::to_value(&old).unwrap();
+
+        // Reorder the nodes, which should not be reported.
+        data["nodes"].as_array_mut().unwrap().reverse();
+        data["nodes"][0]["max_flow"] = serde_json::json!(12.5);
+        data["nodes"][1]["cost"] = serde_json::json!(2.0);
+        data["nodes"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!({"name": "demand2", "type": "Output"}));
+        data["edges"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!(["link1", "demand2"]));
+        data["timestepper"]["end"] = serde_json::json!("2016-12-31");
+        data["scenario_combinations"] = serde_json::json!([[0], [1]]);
+        let new: PywrModel = serde_json::from_value(data).unwrap();
+
+        let changes = old.diff(&new);
+        let lines: Vec<String> = changes.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "~ timestepper\n    timestepper.end: \"2015-12-31\" -> \"2016-12-31\"",
+                "~ scenario combinations\n    scenario_combinations: (none) -> [[0],[1]]",
+                "~ node `link1`\n    link1.cost: (none) -> 2.0",
+                "~ node `demand1`\n    demand1.max_flow: 10.0 -> 12.5",
+                "+ node `demand2`",
+                "+ edge `link1` -> `demand2`",
+            ]
+        );
+
+        // The reverse diff swaps additions and removals.
+        let reverse = new.diff(&old);
+        assert!(reverse.iter().any(
+            |c| c.component == DiffComponent::Node("demand2".to_string())
+                && c.kind == ChangeKind::Removed
+        ));
+
+        // Changes within the scenario combinations are given by their index.
+        let mut changed = new.clone();
+        changed.scenario_combinations = Some(vec![vec![0], vec![2]]);
+        let changes = new.diff(&changed);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(
+            changes[0].to_string(),
+            "~ scenario combinations\n    scenario_combinations[1][0]: 1 -> 2"
+        );
+        match &changes[0].kind {
+            ChangeKind::Changed(attributes) => assert_eq!(
+                attributes[0].attribute,
+                JsonPath::from(vec![PathSegment::Index(1), PathSegment::Index(0)])
+            ),
+            kind => panic!("Unexpected change: {kind:?}"),
+        }
+    }
+}
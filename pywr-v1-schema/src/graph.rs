@@ -0,0 +1,363 @@
+use crate::model::PywrNetwork;
+use std::collections::{HashMap, VecDeque};
+
+/// A directed graph of a network's nodes and edges.
+///
+/// Vertices are the network's non-virtual nodes, in the order they are defined in the network.
+/// Virtual nodes (e.g. aggregated or virtual storage nodes) do not take part in the flow of
+/// the network, and are instead available with their member nodes via
+/// [`NetworkGraph::virtual_nodes`].
+///
+/// Edges that reference missing or virtual nodes are ignored; these are reported by
+/// [`PywrNetwork::validate`].
+pub struct NetworkGraph<'a> {
+    names: Vec<&'a str>,
+    indices: HashMap<&'a str, usize>,
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+    virtual_nodes: Vec<(&'a str, Vec<&'a str>)>,
+}
+
+impl PywrNetwork {
+    /// Create a graph of this network's nodes and edges.
+    pub fn graph(&self) -> NetworkGraph<'_> {
+        NetworkGraph::new(self)
+    }
+}
+
+impl<'a> NetworkGraph<'a> {
+    fn new(network: &'a PywrNetwork) -> Self {
+        let mut names = Vec::new();
+        let mut indices = HashMap::new();
+        let mut virtual_nodes = Vec::new();
+
+        for node in network.nodes.iter().flatten() {
+            if node.is_virtual() {
+                let mut references: Vec<_> = node.node_references().into_iter().collect();
+                references.sort_by_key(|(attribute, _)| *attribute);
+                let members = references.into_iter().flat_map(|(_, n)| n).collect();
+                virtual_nodes.push((node.name(), members));
+            } else if !indices.contains_key(node.name()) {
+                indices.insert(node.name(), names.len());
+                names.push(node.name());
+            }
+        }
+
+        let mut successors = vec![Vec::new(); names.len()];
+        let mut predecessors = vec![Vec::new(); names.len()];
+
+        for edge in network.edges.iter().flatten() {
+            let (from, to) = match (
+                indices.get(edge.from_node.as_str()),
+                indices.get(edge.to_node.as_str()),
+            ) {
+                (Some(from), Some(to)) => (*from, *to),
+                _ => continue,
+            };
+
+            // Edges between the same nodes (e.g. via different slots) are only added once.
+            if !successors[from].contains(&to) {
+                successors[from].push(to);
+                predecessors[to].push(from);
+            }
+        }
+
+        Self {
+            names,
+            indices,
+            successors,
+            predecessors,
+            virtual_nodes,
+        }
+    }
+
+    /// Return the names of the graph's nodes.
+    pub fn nodes(&self) -> &[&'a str] {
+        &self.names
+    }
+
+    /// Return true if the graph contains a node with the given name.
+    pub fn contains_node(&self, name: &str) -> bool {
+        self.indices.contains_key(name)
+    }
+
+    /// Return the names of the nodes connected by an edge from the given node, or `None`
+    /// if the node is not in the graph.
+    pub fn successors(&self, name: &str) -> Option<Vec<&'a str>> {
+        let idx = self.indices.get(name)?;
+        Some(self.to_names(&self.successors[*idx]))
+    }
+
+    /// Return the names of the nodes connected by an edge to the given node, or `None`
+    /// if the node is not in the graph.
+    pub fn predecessors(&self, name: &str) -> Option<Vec<&'a str>> {
+        let idx = self.indices.get(name)?;
+        Some(self.to_names(&self.predecessors[*idx]))
+    }
+
+    /// Return the names of all nodes that the given node can reach, ordered by their distance
+    /// from the node, or `None` if the node is not in the graph.
+    ///
+    /// The node itself is only included if it is part of a cycle.
+    pub fn downstream(&self, name: &str) -> Option<Vec<&'a str>> {
+        let idx = self.indices.get(name)?;
+        Some(self.to_names(&self.reachable(*idx, &self.successors)))
+    }
+
+    /// Return the names of all nodes that can reach the given node, ordered by their distance
+    /// from the node, or `None` if the node is not in the graph.
+    ///
+    /// The node itself is only included if it is part of a cycle.
+    pub fn upstream(&self, name: &str) -> Option<Vec<&'a str>> {
+        let idx = self.indices.get(name)?;
+        Some(self.to_names(&self.reachable(*idx, &self.predecessors)))
+    }
+
+    /// Return the names of the nodes without any incoming edges.
+    pub fn sources(&self) -> Vec<&'a str> {
+        (0..self.names.len())
+            .filter(|idx| self.predecessors[*idx].is_empty())
+            .map(|idx| self.names[idx])
+            .collect()
+    }
+
+    /// Return the names of the nodes without any outgoing edges.
+    pub fn sinks(&self) -> Vec<&'a str> {
+        (0..self.names.len())
+            .filter(|idx| self.successors[*idx].is_empty())
+            .map(|idx| self.names[idx])
+            .collect()
+    }
+
+    /// Return the (weakly) connected components of the graph.
+    ///
+    /// Components, and the nodes within each component, are ordered by the position of the
+    /// nodes in the network.
+    pub fn connected_components(&self) -> Vec<Vec<&'a str>> {
+        let mut component_of = vec![None; self.names.len()];
+        let mut components: Vec<Vec<usize>> = Vec::new();
+
+        for start in 0..self.names.len() {
+            if component_of[start].is_some() {
+                continue;
+            }
+
+            let component = components.len();
+            let mut members = Vec::new();
+            let mut stack = vec![start];
+            component_of[start] = Some(component);
+
+            while let Some(idx) = stack.pop() {
+                members.push(idx);
+                for next in self.successors[idx].iter().chain(&self.predecessors[idx]) {
+                    if component_of[*next].is_none() {
+                        component_of[*next] = Some(component);
+                        stack.push(*next);
+                    }
+                }
+            }
+
+            members.sort_unstable();
+            components.push(members);
+        }
+
+        components.iter().map(|c| self.to_names(c)).collect()
+    }
+
+    /// Return the cycles in the graph.
+    ///
+    /// Each cycle is a strongly connected component with more than one node, or a single node
+    /// with an edge to itself. Cycles, and the nodes within each cycle, are ordered by the
+    /// position of the nodes in the network.
+    pub fn cycles(&self) -> Vec<Vec<&'a str>> {
+        let mut cycles: Vec<Vec<usize>> = strongly_connected_components(&self.successors)
+            .into_iter()
+            .filter(|c| c.len() > 1 || self.successors[c[0]].contains(&c[0]))
+            .map(|mut c| {
+                c.sort_unstable();
+                c
+            })
+            .collect();
+
+        cycles.sort_unstable_by_key(|c| c[0]);
+        cycles.iter().map(|c| self.to_names(c)).collect()
+    }
+
+    /// Return true if the graph contains any cycles.
+    pub fn is_cyclic(&self) -> bool {
+        !self.cycles().is_empty()
+    }
+
+    /// Return the virtual nodes of the network and the names of the nodes they reference.
+    pub fn virtual_nodes(&self) -> &[(&'a str, Vec<&'a str>)] {
+        &self.virtual_nodes
+    }
+
+    fn to_names(&self, indices: &[usize]) -> Vec<&'a str> {
+        indices.iter().map(|idx| self.names[*idx]).collect()
+    }
+
+    /// Breadth-first search from `start` following the given adjacency lists.
+    fn reachable(&self, start: usize, adjacency: &[Vec<usize>]) -> Vec<usize> {
+        let mut visited = vec![false; self.names.len()];
+        let mut reached = Vec::new();
+        let mut queue: VecDeque<usize> = adjacency[start].iter().copied().collect();
+
+        for idx in &adjacency[start] {
+            visited[*idx] = true;
+        }
+
+        while let Some(idx) = queue.pop_front() {
+            reached.push(idx);
+            for next in &adjacency[idx] {
+                if !visited[*next] {
+                    visited[*next] = true;
+                    queue.push_back(*next);
+                }
+            }
+        }
+
+        reached
+    }
+}
+
+/// Return the strongly connected components of the graph with the given adjacency lists,
+/// using Tarjan's algorithm. This is implemented iteratively to avoid recursion on large
+/// networks.
+pub(crate) fn strongly_connected_components(adjacency: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let n = adjacency.len();
+    let mut index = vec![None; n];
+    let mut low_link = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+    let mut next_index = 0;
+
+    for start in 0..n {
+        if index[start].is_some() {
+            continue;
+        }
+
+        // Each frame is a node and the position of the next successor to visit.
+        let mut frames = vec![(start, 0)];
+        index[start] = Some(next_index);
+        low_link[start] = next_index;
+        next_index += 1;
+        stack.push(start);
+        on_stack[start] = true;
+
+        while let Some((idx, pos)) = frames.last_mut() {
+            let idx = *idx;
+            if let Some(next) = adjacency[idx].get(*pos).copied() {
+                *pos += 1;
+                match index[next] {
+                    None => {
+                        index[next] = Some(next_index);
+                        low_link[next] = next_index;
+                        next_index += 1;
+                        stack.push(next);
+                        on_stack[next] = true;
+                        frames.push((next, 0));
+                    }
+                    Some(next_idx) if on_stack[next] => {
+                        low_link[idx] = low_link[idx].min(next_idx);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            frames.pop();
+            if let Some((parent, _)) = frames.last() {
+                low_link[*parent] = low_link[*parent].min(low_link[idx]);
+            }
+
+            if Some(low_link[idx]) == index[idx] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == idx {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PywrModel;
+    use std::path::PathBuf;
+
+    fn load_test_model(name: &str) -> PywrModel {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("models")
+            .join(name);
+        PywrModel::from_path(path).unwrap()
+    }
+
+    #[test]
+    fn test_simple_graph() {
+        let model = load_test_model("simple1_semi_broken.json");
+        let graph = model.network.graph();
+
+        assert_eq!(
+            graph.nodes(),
+            &["supply1", "link1", "demand1", "supply2", "link2"]
+        );
+        assert_eq!(graph.successors("supply1"), Some(vec!["link1"]));
+        assert_eq!(graph.predecessors("demand1"), Some(vec!["link1"]));
+        assert_eq!(graph.successors("missing"), None);
+        assert_eq!(graph.downstream("supply1"), Some(vec!["link1", "demand1"]));
+        assert_eq!(graph.upstream("demand1"), Some(vec!["link1", "supply1"]));
+        assert_eq!(graph.upstream("supply1"), Some(vec![]));
+        assert_eq!(graph.sources(), vec!["supply1", "supply2"]);
+        assert_eq!(graph.sinks(), vec!["demand1", "link2"]);
+        assert_eq!(
+            graph.connected_components(),
+            vec![
+                vec!["supply1", "link1", "demand1"],
+                vec!["supply2", "link2"]
+            ]
+        );
+        assert!(!graph.is_cyclic());
+    }
+
+    #[test]
+    fn test_virtual_nodes() {
+        let model = load_test_model("aggregated1.json");
+        let graph = model.network.graph();
+
+        assert!(!graph.contains_node("agg"));
+        assert_eq!(graph.virtual_nodes(), &[("agg", vec!["A", "B"])]);
+    }
+
+    #[test]
+    fn test_cycles() {
+        let data = r#"
+            {
+                "nodes": [
+                    {"name": "a", "type": "Link"},
+                    {"name": "b", "type": "Link"},
+                    {"name": "c", "type": "Link"},
+                    {"name": "d", "type": "Link"},
+                    {"name": "e", "type": "Link"}
+                ],
+                "edges": [["a", "b"], ["b", "c"], ["c", "a"], ["c", "d"], ["e", "e"]]
+            }
+            "#;
+        let network: crate::PywrNetwork = serde_json::from_str(data).unwrap();
+        let graph = network.graph();
+
+        assert_eq!(graph.cycles(), vec![vec!["a", "b", "c"], vec!["e"]]);
+        assert_eq!(graph.downstream("a"), Some(vec!["b", "c", "a", "d"]));
+        assert!(graph.sources().is_empty());
+        assert_eq!(graph.sinks(), vec!["d"]);
+    }
+}
@@ -0,0 +1,281 @@
+use crate::PywrSchemaError;
+use crate::model::PywrNetwork;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+impl PywrNetwork {
+    /// Merge the JSON files listed in `includes` into this network.
+    ///
+    /// Include paths are resolved relative to the directory of `path`, which should be the
+    /// location of the file this network was loaded from. Included files may themselves
+    /// include other files. The nodes, edges, parameters, tables and recorders of each
+    /// included file are appended to this network, and an error is returned if an
+    /// include is circular or redefines an existing name. A file that is included more than
+    /// once (e.g. by two included files) is only merged the first time.
+    ///
+    /// Non-JSON includes (e.g. Python files defining custom parameters) are not loaded. They
+    /// are kept in `includes`, relative to `path`, so that they are reported as resources.
+    pub fn load_includes<P: AsRef<Path>>(&mut self, path: P) -> Result<(), PywrSchemaError> {
+        let path = path.as_ref();
+        let mut stack = vec![path.canonicalize()?];
+        let mut merged = HashSet::new();
+
+        self.merge_includes(path, &mut stack, &mut merged)
+    }
+
+    /// Merge the includes of this network, which was loaded from `path`. The canonical paths
+    /// of the files being included are in `stack`, and those already merged in `merged`.
+    fn merge_includes(
+        &mut self,
+        path: &Path,
+        stack: &mut Vec<PathBuf>,
+        merged: &mut HashSet<PathBuf>,
+    ) -> Result<(), PywrSchemaError> {
+        let includes = match self.includes.take() {
+            Some(includes) => includes,
+            None => return Ok(()),
+        };
+
+        let parent = path.parent().unwrap_or_else(|| Path::new(""));
+        let mut remaining = Vec::new();
+
+        for include in includes {
+            if include.extension().is_none_or(|ext| ext != "json") {
+                remaining.push(include);
+                continue;
+            }
+
+            let include_path = parent.join(&include);
+            let canonical = include_path.canonicalize()?;
+            if stack.contains(&canonical) {
+                return Err(PywrSchemaError::CircularInclude(include_path));
+            }
+            if !merged.insert(canonical.clone()) {
+                continue;
+            }
+
+            let mut included = PywrNetwork::from_path(&include_path)?;
+            stack.push(canonical);
+            included.merge_includes(&include_path, stack, merged)?;
+            stack.pop();
+
+            // Any remaining includes of the included file are relative to that file.
+            let include_parent = include.parent().unwrap_or_else(|| Path::new(""));
+            if let Some(nested) = included.includes.take() {
+                remaining.extend(nested.into_iter().map(|p| include_parent.join(p)));
+            }
+
+            self.merge(included, &include_path)?;
+        }
+
+        if !remaining.is_empty() {
+            self.includes = Some(remaining);
+        }
+
+        Ok(())
+    }
+
+    /// Append the contents of `other`, which was loaded from `path`, to this network.
+    fn merge(&mut self, other: PywrNetwork, path: &Path) -> Result<(), PywrSchemaError> {
+        let clash = |component: &'static str, name: &str| PywrSchemaError::IncludeNameClash {
+            path: path.to_path_buf(),
+            component,
+            name: name.to_string(),
+        };
+
+        if let Some(other_nodes) = other.nodes {
+            let nodes = self.nodes.get_or_insert_with(Vec::new);
+            let mut names: HashSet<String> = nodes.iter().map(|n| n.name().to_string()).collect();
+            for node in other_nodes {
+                if !names.insert(node.name().to_string()) {
+                    return Err(clash("node", node.name()));
+                }
+                nodes.push(node);
+            }
+        }
+
+        if let Some(other_edges) = other.edges {
+            self.edges.get_or_insert_with(Vec::new).extend(other_edges);
+        }
+
+        if let Some(other_parameters) = other.parameters {
+            match &mut self.parameters {
+                Some(parameters) => {
+                    let mut names: HashSet<String> = parameters
+                        .iter()
+                        .filter_map(|p| p.name().map(|n| n.to_string()))
+                        .collect();
+                    for parameter in other_parameters {
+                        if let Some(name) = parameter.name()
+                            && !names.insert(name.to_string())
+                        {
+                            return Err(clash("parameter", name));
+                        }
+                        parameters.push(parameter);
+                    }
+                }
+                None => self.parameters = Some(other_parameters),
+            }
+        }
+
+        if let Some(other_tables) = other.tables {
+            match &mut self.tables {
+                Some(tables) => {
+                    let mut names: HashSet<String> =
+                        tables.iter().map(|t| t.name.clone()).collect();
+                    for table in other_tables {
+                        if !names.insert(table.name.clone()) {
+                            return Err(clash("table", &table.name));
+                        }
+                        tables.push(table);
+                    }
+                }
+                None => self.tables = Some(other_tables),
+            }
+        }
+
+        if let Some(other_recorders) = other.recorders {
+            match &mut self.recorders {
+                Some(recorders) => {
+                    let mut names: HashSet<String> =
+                        recorders.iter().map(|r| r.name().to_string()).collect();
+                    for recorder in other_recorders {
+                        if !names.insert(recorder.name().to_string()) {
+                            return Err(clash("recorder", recorder.name()));
+                        }
+                        recorders.push(recorder);
+                    }
+                }
+                None => self.recorders = Some(other_recorders),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PywrModel, PywrSchemaError};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn test_model_path(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("models")
+            .join(name)
+    }
+
+    #[test]
+    fn test_json_include() {
+        let model = PywrModel::from_path_with_includes(test_model_path("extra1.json")).unwrap();
+
+        assert!(model.network.includes.is_none());
+        assert_eq!(model.network.nodes.as_ref().unwrap().len(), 4);
+        assert_eq!(model.network.edges.as_ref().unwrap().len(), 3);
+        assert!(model.get_node_by_name("supply2").is_some());
+        assert!(model.get_parameter_by_name("supply2_max_flow").is_some());
+    }
+
+    #[test]
+    fn test_python_include() {
+        let model =
+            PywrModel::from_path_with_includes(test_model_path("python_include.json")).unwrap();
+
+        assert_eq!(
+            model.network.includes,
+            Some(vec![PathBuf::from("my_parameter.py")])
+        );
+        assert!(
+            model
+                .resource_paths()
+                .contains(&PathBuf::from("my_parameter.py"))
+        );
+    }
+
+    #[test]
+    fn test_diamond_include() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+
+        let model = r#"
+            {
+                "metadata": {"title": "Diamond include"},
+                "timestepper": {"start": "2015-01-01", "end": "2015-12-31", "timestep": 1},
+                "includes": ["a.json", "b.json"],
+                "nodes": [{"name": "supply1", "type": "Input"}]
+            }
+            "#;
+        fs::write(dir.join("model.json"), model).unwrap();
+
+        // `a.json` and `b.json` both include `shared.json`.
+        fs::write(
+            dir.join("a.json"),
+            r#"{"includes": ["shared.json"], "nodes": [{"name": "a", "type": "Link"}]}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("b.json"),
+            r#"{"includes": ["./shared.json"], "nodes": [{"name": "b", "type": "Link"}]}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("shared.json"),
+            r#"{"nodes": [{"name": "demand1", "type": "Output"}]}"#,
+        )
+        .unwrap();
+
+        let model = PywrModel::from_path_with_includes(dir.join("model.json")).unwrap();
+        let names: Vec<_> = model
+            .network
+            .nodes
+            .iter()
+            .flatten()
+            .map(|n| n.name())
+            .collect();
+        assert_eq!(names, vec!["supply1", "a", "demand1", "b"]);
+    }
+
+    #[test]
+    fn test_include_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+
+        let model = r#"
+            {
+                "metadata": {"title": "Include errors"},
+                "timestepper": {"start": "2015-01-01", "end": "2015-12-31", "timestep": 1},
+                "includes": ["a.json"],
+                "nodes": [{"name": "supply1", "type": "Input"}]
+            }
+            "#;
+        fs::write(dir.join("model.json"), model).unwrap();
+
+        // `a.json` includes `b.json`, which includes `a.json` again.
+        fs::write(dir.join("a.json"), r#"{"includes": ["b.json"]}"#).unwrap();
+        fs::write(dir.join("b.json"), r#"{"includes": ["a.json"]}"#).unwrap();
+
+        match PywrModel::from_path_with_includes(dir.join("model.json")) {
+            Err(PywrSchemaError::CircularInclude(path)) => assert!(path.ends_with("a.json")),
+            _ => panic!("Expected a circular include error."),
+        }
+
+        // `a.json` redefines a node in the model.
+        fs::write(
+            dir.join("a.json"),
+            r#"{"nodes": [{"name": "supply1", "type": "Input"}]}"#,
+        )
+        .unwrap();
+
+        match PywrModel::from_path_with_includes(dir.join("model.json")) {
+            Err(PywrSchemaError::IncludeNameClash {
+                component, name, ..
+            }) => {
+                assert_eq!(component, "node");
+                assert_eq!(name, "supply1");
+            }
+            _ => panic!("Expected an include name clash error."),
+        }
+    }
+}
@@ -0,0 +1,389 @@
+use crate::model::PywrNetwork;
+use crate::nodes::Node;
+use crate::parameters::{
+    Parameter, ParameterValue, ParameterValueTypeMut, ParameterVec, visit_parameter_values,
+    visit_parameter_values_mut,
+};
+use crate::recorders::{CoreRecorder, Recorder};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+impl PywrNetwork {
+    /// Move every inline parameter into the network's named parameters, replacing it with a
+    /// reference to the new parameter.
+    ///
+    /// Inline parameters that have a name keep it. Otherwise, following Pywr's convention, the
+    /// parameter is named after its owner and attribute (e.g. `supply1.max_flow`). Parameters
+    /// nested within other inline parameters are hoisted too, and are named after their
+    /// (hoisted) parent (e.g. `supply1.max_flow.parameters[0]`). If a name is already in use
+    /// a numeric suffix is added (e.g. `supply1.max_flow_1`).
+    ///
+    /// Returns the names of the hoisted parameters in the order they were added.
+    pub fn hoist_inline_parameters(&mut self) -> Vec<String> {
+        let mut names: HashSet<String> = self
+            .parameters
+            .iter()
+            .flat_map(|p| p.iter())
+            .filter_map(|p| p.name().map(|n| n.to_string()))
+            .collect();
+        let mut hoisted = Vec::new();
+
+        for node in self.nodes.iter_mut().flatten() {
+            let owner = node.name().to_string();
+            hoist_values(&owner, node.parameters_mut(), &mut names, &mut hoisted);
+        }
+
+        for recorder in self.recorders.iter_mut().flat_map(|r| r.iter_mut()) {
+            let owner = recorder.name().to_string();
+            hoist_values(&owner, recorder.parameters_mut(), &mut names, &mut hoisted);
+        }
+
+        let mut hoisted_names: Vec<String> = hoisted
+            .iter()
+            .filter_map(|p: &Parameter| p.name().map(|n| n.to_string()))
+            .collect();
+
+        let parameters = match &mut self.parameters {
+            Some(parameters) => parameters,
+            None if hoisted.is_empty() => return hoisted_names,
+            None => self
+                .parameters
+                .insert(ParameterVec::with_capacity(hoisted.len())),
+        };
+        parameters.extend(hoisted);
+
+        // Hoisted parameters are appended, so their own inline parameters are hoisted in turn.
+        let mut idx = 0;
+        while idx < parameters.len() {
+            let owner = parameters[idx].name().unwrap_or_default().to_string();
+            let mut hoisted = Vec::new();
+            hoist_values(
+                &owner,
+                parameters[idx].parameters_mut(),
+                &mut names,
+                &mut hoisted,
+            );
+
+            hoisted_names.extend(
+                hoisted
+                    .iter()
+                    .filter_map(|p| p.name().map(|n| n.to_string())),
+            );
+            parameters.extend(hoisted);
+            idx += 1;
+        }
+
+        hoisted_names
+    }
+
+    /// Move each named parameter that is referenced exactly once into the place it is
+    /// referenced, as an inline parameter without a name.
+    ///
+    /// This is the inverse of [`PywrNetwork::hoist_inline_parameters`]. Parameters are not
+    /// inlined if they are referenced in a way that can not hold an inline parameter (e.g. the
+    /// `parameters` of a `TablesRecorder`), could be referenced by a custom component, or are
+    /// only referenced from within themselves.
+    ///
+    /// Returns the names of the inlined parameters in the order they were inlined.
+    pub fn inline_single_use_parameters(&mut self) -> Vec<String> {
+        let (mut references, fixed) = self.parameter_references();
+        let names: Vec<String> = self
+            .parameters
+            .iter()
+            .flat_map(|p| p.iter())
+            .filter_map(|p| p.name().map(|n| n.to_string()))
+            .collect();
+        let mut inlined = Vec::new();
+
+        // Inlining a parameter only moves its own references into the owner of its reference,
+        // so no parameter can become inlinable after it has been passed over.
+        for name in names {
+            let owner = match references.get(&name) {
+                Some((1, owner)) if !fixed.contains(&name) && owner.as_ref() != Some(&name) => {
+                    owner.clone()
+                }
+                _ => continue,
+            };
+
+            let parameters = self.parameters.as_mut().expect("Parameter exists.");
+            let idx = parameters
+                .iter()
+                .position(|p| p.name() == Some(name.as_str()))
+                .expect("Parameter exists.");
+
+            let mut parameter = parameters.remove(idx);
+            parameter.set_name(None);
+            if parameters.is_empty() {
+                self.parameters = None;
+            }
+
+            visit_parameter_values(parameter.parameters(), &mut |_, v| {
+                if let ParameterValue::Reference(n) = v
+                    && let Some(entry) = references.get_mut(n)
+                {
+                    entry.1 = owner.clone();
+                }
+            });
+
+            self.replace_reference(&name, owner.as_deref(), parameter);
+            inlined.push(name);
+        }
+
+        inlined
+    }
+
+    /// Return the number of references to each parameter and the owner of the last
+    /// reference, and the names of the parameters that can not be inlined.
+    fn parameter_references(&self) -> (References, HashSet<String>) {
+        let mut references = HashMap::new();
+        let mut fixed: HashSet<&str> = HashSet::new();
+
+        for node in self.nodes.iter().flatten() {
+            visit_parameter_values(node.parameters(), &mut |_, v| {
+                count_reference(&mut references, None, v)
+            });
+            if let Node::Custom(n) = node {
+                fixed.extend(n.attributes.values().flat_map(strings));
+            }
+        }
+
+        for parameter in self.parameters.iter().flat_map(|p| p.iter()) {
+            let owner = parameter.name();
+            visit_parameter_values(parameter.parameters(), &mut |_, v| {
+                count_reference(&mut references, owner, v)
+            });
+            if let Parameter::Custom(p) = parameter {
+                fixed.extend(p.attributes.values().flat_map(strings));
+            }
+        }
+
+        for recorder in self.recorders.iter().flat_map(|r| r.iter()) {
+            visit_parameter_values(recorder.parameters(), &mut |_, v| {
+                count_reference(&mut references, None, v)
+            });
+            match recorder {
+                Recorder::Core(CoreRecorder::Tables(r)) => {
+                    fixed.extend(r.parameters.iter().flatten().map(|e| e.name()))
+                }
+                Recorder::Custom(r) => fixed.extend(r.attributes.values().flat_map(strings)),
+                Recorder::Core(_) => {}
+            }
+        }
+
+        let fixed = fixed.into_iter().map(|name| name.to_string()).collect();
+        (references, fixed)
+    }
+
+    /// Replace the reference to `name` within `owner`, or within a node or recorder if `owner`
+    /// is `None`, with `parameter` as an inline parameter.
+    fn replace_reference(&mut self, name: &str, owner: Option<&str>, parameter: Parameter) {
+        let mut parameter = Some(parameter);
+        let mut replace = |_: &str, value: &mut ParameterValue| {
+            if matches!(value, ParameterValue::Reference(n) if n == name)
+                && let Some(p) = parameter.take()
+            {
+                *value = ParameterValue::Inline(Box::new(p));
+            }
+        };
+
+        if let Some(owner) = owner {
+            let parameters = self.parameters.iter_mut().flat_map(|p| p.iter_mut());
+            for p in parameters.filter(|p| p.name() == Some(owner)) {
+                visit_parameter_values_mut(p.parameters_mut(), &mut replace);
+            }
+            return;
+        }
+
+        for node in self.nodes.iter_mut().flatten() {
+            visit_parameter_values_mut(node.parameters_mut(), &mut replace);
+        }
+
+        for recorder in self.recorders.iter_mut().flat_map(|r| r.iter_mut()) {
+            visit_parameter_values_mut(recorder.parameters_mut(), &mut replace);
+        }
+    }
+}
+
+/// Replace the inline parameters in `values` with references, adding the parameters
+/// to `hoisted`.
+fn hoist_values(
+    owner: &str,
+    values: HashMap<&str, ParameterValueTypeMut>,
+    names: &mut HashSet<String>,
+    hoisted: &mut Vec<Parameter>,
+) {
+    visit_parameter_values_mut(values, &mut |path, value| {
+        let parameter = match value {
+            ParameterValue::Inline(parameter) => parameter,
+            _ => return,
+        };
+
+        let base = match parameter.name() {
+            Some(name) => name.to_string(),
+            None => format!("{owner}.{path}"),
+        };
+        let mut name = base.clone();
+        let mut suffix = 1;
+        while names.contains(&name) {
+            name = format!("{base}_{suffix}");
+            suffix += 1;
+        }
+
+        parameter.set_name(Some(&name));
+        names.insert(name.clone());
+
+        // The replaced value is not visited further; its inline parameters are hoisted
+        // when the hoisted parameter itself is visited.
+        if let ParameterValue::Inline(parameter) =
+            std::mem::replace(value, ParameterValue::Reference(name))
+        {
+            hoisted.push(*parameter);
+        }
+    });
+}
+
+/// The number of references to each parameter, and the owner of the last reference.
+type References = HashMap<String, (usize, Option<String>)>;
+
+/// Count a reference to a parameter made by `value` within `owner`.
+fn count_reference(references: &mut References, owner: Option<&str>, value: &ParameterValue) {
+    if let ParameterValue::Reference(name) = value {
+        let entry = references.entry(name.clone()).or_insert((0, None));
+        entry.0 += 1;
+        entry.1 = owner.map(|o| o.to_string());
+    }
+}
+
+/// Return all the strings within a JSON value.
+pub(crate) fn strings(value: &Value) -> Vec<&str> {
+    match value {
+        Value::String(s) => vec![s.as_str()],
+        Value::Array(values) => values.iter().flat_map(strings).collect(),
+        Value::Object(map) => map.values().flat_map(strings).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parameters::ParameterValue;
+    use crate::{PywrModel, PywrNetwork};
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_hoist_and_inline_parameters() {
+        let data = r#"
+            {
+                "nodes": [
+                    {
+                        "name": "supply1",
+                        "type": "Input",
+                        "max_flow": {
+                            "type": "aggregated",
+                            "agg_func": "min",
+                            "parameters": [
+                                "p1",
+                                {"type": "monthlyprofile", "values": [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]}
+                            ]
+                        }
+                    },
+                    {"name": "demand1", "type": "Output", "cost": {"type": "constant", "value": -10}}
+                ],
+                "parameters": {
+                    "p1": {"type": "constant", "value": 5.0},
+                    "demand1.cost": {"type": "constant", "value": 1.0}
+                }
+            }
+            "#;
+        let mut network: PywrNetwork = serde_json::from_str(data).unwrap();
+        let original = serde_json::to_value(&network).unwrap();
+
+        let hoisted = network.hoist_inline_parameters();
+        assert_eq!(
+            hoisted,
+            vec![
+                "supply1.max_flow",
+                "demand1.cost_1",
+                "supply1.max_flow.parameters[1]"
+            ]
+        );
+
+        let node = network.get_node_by_name("supply1").unwrap();
+        assert!(matches!(
+            node.parameters()["max_flow"],
+            crate::parameters::ParameterValueType::Single(ParameterValue::Reference(name)) if name == "supply1.max_flow"
+        ));
+        assert_eq!(
+            network.parameter_graph().dependencies("supply1.max_flow"),
+            Some(vec!["p1", "supply1.max_flow.parameters[1]"])
+        );
+
+        // `p1` is only referenced once, so is inlined along with the hoisted parameters.
+        let inlined = network.inline_single_use_parameters();
+        assert_eq!(
+            inlined,
+            vec![
+                "p1",
+                "supply1.max_flow",
+                "demand1.cost_1",
+                "supply1.max_flow.parameters[1]"
+            ]
+        );
+
+        let mut expected = original;
+        expected["nodes"][0]["max_flow"]["parameters"][0] =
+            serde_json::json!({"type": "Constant", "value": 5.0});
+        expected["parameters"].as_object_mut().unwrap().remove("p1");
+        assert_eq!(serde_json::to_value(&network).unwrap(), expected);
+    }
+
+    /// A parameter whose only reference has been moved within itself is not inlined.
+    #[test]
+    fn test_inline_cycle() {
+        let data = r#"
+            {
+                "nodes": [{"name": "supply1", "type": "Input"}],
+                "parameters": {
+                    "a": {"type": "aggregated", "agg_func": "min", "parameters": ["b"]},
+                    "b": {"type": "aggregated", "agg_func": "min", "parameters": ["a"]}
+                }
+            }
+            "#;
+        let mut network: PywrNetwork = serde_json::from_str(data).unwrap();
+
+        assert_eq!(network.inline_single_use_parameters(), vec!["a"]);
+        assert!(network.get_parameter_by_name("b").is_some());
+        assert!(network.inline_single_use_parameters().is_empty());
+    }
+
+    /// Hoisting the inline parameters of the test models should not introduce any
+    /// validation errors.
+    #[test]
+    fn test_hoist_pywr_models() {
+        let test_models_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("models");
+
+        for entry in fs::read_dir(test_models_dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|ext| ext != "json") || path.ends_with("extra2.json") {
+                continue;
+            }
+
+            let mut model = PywrModel::from_path_with_includes(&path).unwrap();
+            let issues = model.validate();
+            model.network.hoist_inline_parameters();
+
+            assert_eq!(model.validate(), issues, "{}", path.display());
+            assert!(
+                model
+                    .network
+                    .parameter_graph()
+                    .parameters()
+                    .iter()
+                    .all(|name| { model.get_parameter_by_name(name).is_some() })
+            );
+        }
+    }
+}
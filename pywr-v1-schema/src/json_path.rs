@@ -0,0 +1,85 @@
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+/// A segment of the path to a value in a JSON document.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    /// The value of a key in an object.
+    Key(String),
+    /// The element at an index of an array.
+    Index(usize),
+}
+
+/// The path to a value in a JSON document, which is displayed with keys separated by `.` and
+/// indices in brackets (e.g. `max_flow.parameters[1]`). The path of the whole document is
+/// empty.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct JsonPath(Vec<PathSegment>);
+
+impl JsonPath {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl From<Vec<PathSegment>> for JsonPath {
+    fn from(segments: Vec<PathSegment>) -> Self {
+        Self(segments)
+    }
+}
+
+impl FromIterator<PathSegment> for JsonPath {
+    fn from_iter<I: IntoIterator<Item = PathSegment>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Deref for JsonPath {
+    type Target = Vec<PathSegment>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for JsonPath {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Key(key) if i == 0 => write!(f, "{key}")?,
+                PathSegment::Key(key) => write!(f, ".{key}")?,
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JsonPath, PathSegment};
+
+    #[test]
+    fn test_display() {
+        assert_eq!(JsonPath::new().to_string(), "");
+
+        let path = JsonPath::from(vec![
+            PathSegment::Key("max_flow".to_string()),
+            PathSegment::Key("parameters".to_string()),
+            PathSegment::Index(1),
+        ]);
+        assert_eq!(path.to_string(), "max_flow.parameters[1]");
+
+        let path = JsonPath::from(vec![
+            PathSegment::Index(0),
+            PathSegment::Key("a".to_string()),
+        ]);
+        assert_eq!(path.to_string(), "[0].a");
+    }
+}
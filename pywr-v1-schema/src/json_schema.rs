@@ -0,0 +1,204 @@
+use crate::edge::Edge;
+use crate::model::{PywrModel, PywrMultiModel, PywrNetwork};
+use crate::parameters::{Parameter, ParameterVec};
+use crate::recorders::{Recorder, RecorderVec};
+use crate::tables::{Table, TableVec};
+use schemars::transform::{Transform, transform_subschemas};
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema, schema_for};
+use serde_json::Value;
+use std::borrow::Cow;
+
+/// Return the JSON Schema of a Pywr model file.
+pub fn model_schema() -> Schema {
+    schema_for!(PywrModel)
+}
+
+/// Return the JSON Schema of a Pywr file containing multiple models.
+pub fn multi_model_schema() -> Schema {
+    schema_for!(PywrMultiModel)
+}
+
+/// Return the JSON Schema of a Pywr network, such as a file included by a model.
+pub fn network_schema() -> Schema {
+    schema_for!(PywrNetwork)
+}
+
+/// Add the aliases of each variant of an internally tagged enum to the schema of its `type`
+/// tag, so that every spelling accepted by deserialization is valid.
+pub(crate) struct TypeAliases(pub &'static [(&'static str, &'static [&'static str])]);
+
+impl Transform for TypeAliases {
+    fn transform(&mut self, schema: &mut Schema) {
+        if let Some(tag) = schema
+            .get_mut("properties")
+            .and_then(|p| p.get_mut("type"))
+            .and_then(|t| t.as_object_mut())
+            && let Some(Value::String(name)) = tag.get("const")
+            && let Some((_, aliases)) = self.0.iter().find(|(n, _)| n == name)
+        {
+            let names = std::iter::once(name.clone())
+                .chain(aliases.iter().map(|a| a.to_string()))
+                .map(Value::String)
+                .collect();
+            tag.remove("const");
+            tag.insert("enum".to_string(), Value::Array(names));
+        }
+
+        transform_subschemas(self, schema);
+    }
+}
+
+impl JsonSchema for Edge {
+    fn schema_name() -> Cow<'static, str> {
+        "Edge".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "An edge from one node to another, optionally with the slots it connects.",
+            "type": "array",
+            "oneOf": [
+                {
+                    "prefixItems": [{"type": "string"}, {"type": "string"}],
+                    "minItems": 2,
+                    "maxItems": 2
+                },
+                {
+                    "prefixItems": [
+                        {"type": "string"},
+                        {"type": "string"},
+                        {"type": ["string", "null"]},
+                        {"type": ["string", "null"]}
+                    ],
+                    "minItems": 4,
+                    "maxItems": 4
+                }
+            ]
+        })
+    }
+}
+
+/// The schema of a map of components keyed by their name.
+fn named_map_schema<T: JsonSchema>(generator: &mut SchemaGenerator, description: &str) -> Schema {
+    json_schema!({
+        "description": description,
+        "type": "object",
+        "additionalProperties": generator.subschema_for::<T>()
+    })
+}
+
+impl JsonSchema for ParameterVec {
+    fn schema_name() -> Cow<'static, str> {
+        "ParameterVec".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        named_map_schema::<Parameter>(generator, "Parameters keyed by name.")
+    }
+}
+
+impl JsonSchema for TableVec {
+    fn schema_name() -> Cow<'static, str> {
+        "TableVec".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        named_map_schema::<Table>(generator, "Tables keyed by name.")
+    }
+}
+
+impl JsonSchema for RecorderVec {
+    fn schema_name() -> Cow<'static, str> {
+        "RecorderVec".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        named_map_schema::<Recorder>(generator, "Recorders keyed by name.")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{model_schema, multi_model_schema, network_schema};
+    use serde_json::{Value, json};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn test_model_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("models")
+    }
+
+    /// All of the test models should be valid against the generated schema.
+    #[test]
+    fn test_pywr_models_match_schema() {
+        let model_validator = jsonschema::validator_for(model_schema().as_value()).unwrap();
+        let network_validator = jsonschema::validator_for(network_schema().as_value()).unwrap();
+
+        for entry in fs::read_dir(test_model_dir()).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+
+            let data: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+            // `extra2.json` is a network included by `extra1.json` rather than a model.
+            let validator = match path.ends_with("extra2.json") {
+                true => &network_validator,
+                false => &model_validator,
+            };
+
+            let errors: Vec<_> = validator
+                .iter_errors(&data)
+                .map(|e| e.to_string())
+                .collect();
+            assert!(
+                errors.is_empty(),
+                "{path:?} does not match the schema: {errors:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_type_aliases() {
+        let schema = model_schema();
+        let tags: Vec<&Value> = schema.as_value()["$defs"]["CoreNode"]["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| &v["properties"]["type"]["enum"])
+            .collect();
+
+        assert!(tags.contains(&&json!(["Input", "input"])));
+        assert!(tags.contains(&&json!([
+            "RiverSplitWithGauge",
+            "riversplitwithgauge",
+            "Riversplitwithgauge"
+        ])));
+    }
+
+    #[test]
+    fn test_edges() {
+        let validator = jsonschema::validator_for(network_schema().as_value()).unwrap();
+
+        assert!(validator.is_valid(&json!({"edges": [["a", "b"], ["a", "b", "s1", null]]})));
+        assert!(!validator.is_valid(&json!({"edges": [["a", "b", "s1"]]})));
+        assert!(!validator.is_valid(&json!({"edges": [["a", 1]]})));
+    }
+
+    #[test]
+    fn test_multi_model_schema() {
+        let validator = jsonschema::validator_for(multi_model_schema().as_value()).unwrap();
+
+        let mut data = json!({
+            "metadata": {"title": "Multi-model"},
+            "timestepper": {"start": "2015-01-01", "end": "2015-12-31", "timestep": 1},
+            "models": [{"name": "a", "path": "a.json"}]
+        });
+        assert!(validator.is_valid(&data));
+
+        data["models"] = json!([{"path": "a.json"}]);
+        assert!(!validator.is_valid(&data));
+    }
+}
@@ -1,8 +1,28 @@
+pub mod bundle;
+pub mod diff;
 pub mod edge;
+pub mod graph;
+mod includes;
+mod inline;
+pub mod json_path;
+#[cfg(feature = "json-schema")]
+pub mod json_schema;
+mod location;
+pub mod lossless;
+pub mod merge;
 pub mod model;
 pub mod nodes;
 pub mod parameters;
+pub mod recorders;
+mod recover;
+mod rename;
+pub mod resources;
+pub mod scenarios;
+pub mod strict;
 pub mod tables;
+pub mod timestep;
+pub mod unused;
+pub mod validation;
 
 pub use model::{PywrModel, PywrMultiModel, PywrNetwork};
 use std::io;
@@ -11,14 +31,65 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum PywrSchemaError {
-    #[error("An invalid URL was found.")]
-    InvalidUrlFound,
-    #[error("data store disconnected")]
+    #[error("An invalid URL was found: {0}")]
+    InvalidUrlFound(String),
+    #[error("IO error: {0}")]
     IoError(#[from] io::Error),
     #[error("Serde error")]
     SerdeError(#[from] serde_json::Error),
+    #[error(
+        "{message} {}(line {line}, column {column})",
+        if .path.is_empty() { String::new() } else { format!("at `{}` ", .path) }
+    )]
+    DeserializationError {
+        message: String,
+        /// The path of the value that failed to deserialize (e.g. `nodes[3].max_flow`), or
+        /// an empty string if the document could not be parsed.
+        path: String,
+        line: usize,
+        /// The column of the error on its line, counted in bytes.
+        column: usize,
+    },
     #[error("Resource not found on local host: {0}")]
     LocalResourceNotFound(PathBuf),
+    #[error("Resource path could not be rebased: {}", .0.display())]
+    ResourceNotRebased(PathBuf),
+    #[error("Scenario combination {index} is invalid: {message}")]
+    InvalidScenarioCombination { index: usize, message: String },
+    #[error("Invalid timestep frequency: `{0}`")]
+    InvalidFrequency(String),
+    #[error("The timestepper start ({start}) is after its end ({end})")]
+    TimestepperStartAfterEnd {
+        start: model::DateType,
+        end: model::DateType,
+    },
     #[error("Invalid Pywr format")]
     InvalidPywrDataFormat,
+    #[error("The file is a multi-model file, with a list of `models`")]
+    MultiModelFile,
+    #[error("Circular include of file: {}", .0.display())]
+    CircularInclude(PathBuf),
+    #[error("Included file ({}) redefines {component} `{name}`", .path.display())]
+    IncludeNameClash {
+        path: PathBuf,
+        component: &'static str,
+        name: String,
+    },
+    #[error("Circular parameter reference: {}", .0.join(" -> "))]
+    CircularParameterReference(Vec<String>),
+    #[error("No {component} named `{name}` found")]
+    ComponentNotFound {
+        component: &'static str,
+        name: String,
+    },
+    #[error("A {component} named `{name}` already exists")]
+    NameAlreadyExists {
+        component: &'static str,
+        name: String,
+    },
+    #[error(
+        "Components could not be deserialized as their core type:\n{}",
+        .0.iter().map(|m| m.to_string()).collect::<Vec<_>>().join("\n")
+    )]
+    CoreTypeMismatch(Vec<strict::CoreTypeMismatch>),
 }
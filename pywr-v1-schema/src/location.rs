@@ -0,0 +1,338 @@
+use crate::PywrSchemaError;
+use crate::json_path::{JsonPath, PathSegment};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use serde_path_to_error::Segment;
+
+/// Parse a JSON document, returning a [`PywrSchemaError::DeserializationError`] with the
+/// position of any syntax error.
+pub(crate) fn parse_json(source: &str) -> Result<Value, PywrSchemaError> {
+    serde_json::from_str(source).map_err(|error| PywrSchemaError::DeserializationError {
+        message: error_message(&error),
+        path: String::new(),
+        line: error.line(),
+        column: error.column(),
+    })
+}
+
+/// Deserialize `value`, which was parsed from `source`, as `T`.
+///
+/// Any error is returned as a [`PywrSchemaError::DeserializationError`] with the path of the
+/// value that failed to deserialize, and the line and column at which that value starts in
+/// `source`.
+pub(crate) fn deserialize_value<T: DeserializeOwned>(
+    source: &str,
+    value: Value,
+) -> Result<T, PywrSchemaError> {
+    deserialize_value_at(source, value, &[])
+}
+
+/// Deserialize `value`, which is found at `prefix` in the JSON document `source`, as `T`.
+///
+/// See [`deserialize_value`] for details of the error returned.
+pub(crate) fn deserialize_value_at<T: DeserializeOwned>(
+    source: &str,
+    value: Value,
+    prefix: &[PathSegment],
+) -> Result<T, PywrSchemaError> {
+    serde_path_to_error::deserialize(value).map_err(|error| {
+        // Enum variants are ignored, as they do not appear in the JSON.
+        let path: Vec<_> = prefix
+            .iter()
+            .cloned()
+            .chain(
+                error
+                    .path()
+                    .iter()
+                    .map_while(|segment| match segment {
+                        Segment::Map { key } => Some(Some(PathSegment::Key(key.clone()))),
+                        Segment::Seq { index } => Some(Some(PathSegment::Index(*index))),
+                        Segment::Enum { .. } => Some(None),
+                        Segment::Unknown => None,
+                    })
+                    .flatten(),
+            )
+            .collect();
+
+        error_at(source, &path, error_message(error.inner()))
+    })
+}
+
+/// Return a [`PywrSchemaError::DeserializationError`] with `message` for the value at `path`
+/// in the JSON document `source`.
+pub(crate) fn error_at(source: &str, path: &[PathSegment], message: String) -> PywrSchemaError {
+    let (line, column) = locate(source, path).unwrap_or((0, 0));
+
+    PywrSchemaError::DeserializationError {
+        message,
+        path: JsonPath::from(path.to_vec()).to_string(),
+        line,
+        column,
+    }
+}
+
+/// The message of a serde error without the position that is appended to it.
+fn error_message(error: &serde_json::Error) -> String {
+    let message = error.to_string();
+    match message.rfind(" at line ") {
+        Some(i) if error.line() > 0 => message[..i].to_string(),
+        _ => message,
+    }
+}
+
+/// Find the line and column (both starting at 1) at which the value at `path` starts in the
+/// JSON document `source`. Returns `None` if the path does not exist.
+///
+/// As with the syntax errors of `serde_json`, the column is counted in bytes.
+pub(crate) fn locate(source: &str, path: &[PathSegment]) -> Option<(usize, usize)> {
+    let mut scanner = Scanner {
+        source: source.as_bytes(),
+        pos: 0,
+    };
+    scanner.skip_whitespace();
+
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) => scanner.enter_object(key)?,
+            PathSegment::Index(index) => scanner.enter_array(*index)?,
+        }
+    }
+
+    let before = &source[..scanner.pos];
+    let line = before.matches('\n').count() + 1;
+    let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+
+    Some((line, column))
+}
+
+/// A minimal JSON scanner that moves between the values of a valid JSON document.
+struct Scanner<'a> {
+    source: &'a [u8],
+    pos: usize,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.source.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consume `c`, and any whitespace that follows it.
+    fn expect(&mut self, c: u8) -> Option<()> {
+        if self.peek() != Some(c) {
+            return None;
+        }
+        self.pos += 1;
+        self.skip_whitespace();
+        Some(())
+    }
+
+    /// Move to the value of `key` in the object at the current position.
+    fn enter_object(&mut self, key: &str) -> Option<()> {
+        self.expect(b'{')?;
+        loop {
+            let start = self.pos;
+            self.skip_string()?;
+            let found = serde_json::from_slice::<String>(&self.source[start..self.pos]).ok()?;
+            self.skip_whitespace();
+            self.expect(b':')?;
+            if found == key {
+                return Some(());
+            }
+            self.skip_value()?;
+            self.expect(b',')?;
+        }
+    }
+
+    /// Move to the element at `index` in the array at the current position.
+    fn enter_array(&mut self, index: usize) -> Option<()> {
+        self.expect(b'[')?;
+        for _ in 0..index {
+            self.skip_value()?;
+            self.expect(b',')?;
+        }
+        (self.peek() != Some(b']')).then_some(())
+    }
+
+    fn skip_string(&mut self) -> Option<()> {
+        if self.peek() != Some(b'"') {
+            return None;
+        }
+        self.pos += 1;
+        loop {
+            match self.peek()? {
+                b'"' => break,
+                b'\\' => self.pos += 2,
+                _ => self.pos += 1,
+            }
+        }
+        self.pos += 1;
+        Some(())
+    }
+
+    /// Skip the value at the current position, and any whitespace that follows it.
+    fn skip_value(&mut self) -> Option<()> {
+        match self.peek()? {
+            b'"' => self.skip_string()?,
+            b'{' | b'[' => {
+                let mut depth = 0usize;
+                loop {
+                    match self.peek()? {
+                        b'"' => {
+                            self.skip_string()?;
+                            continue;
+                        }
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => depth -= 1,
+                        _ => {}
+                    }
+                    self.pos += 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+            }
+            // Numbers and literals
+            _ => {
+                while self
+                    .peek()
+                    .is_some_and(|c| !matches!(c, b',' | b'}' | b']') && !c.is_ascii_whitespace())
+                {
+                    self.pos += 1;
+                }
+            }
+        }
+        self.skip_whitespace();
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{deserialize_value, parse_json};
+    use crate::PywrSchemaError;
+    use crate::model::{PywrModel, PywrNetwork};
+    use std::fs;
+
+    #[test]
+    fn test_error_location() {
+        let source = r#"{
+    "nodes": [
+        {"name": "supply1", "type": "Input"},
+        {"type": "Output"}
+    ],
+    "parameters": {
+        "p1": {"type": "constant", "value": 1.0},
+        "p2": {"value": 2.0}
+    }
+}"#;
+
+        let value = parse_json(source).unwrap();
+        let error = deserialize_value::<PywrNetwork>(source, value)
+            .err()
+            .unwrap();
+
+        match error {
+            PywrSchemaError::DeserializationError {
+                path, line, column, ..
+            } => {
+                assert_eq!(path, "nodes[1]");
+                assert_eq!((line, column), (4, 9));
+            }
+            e => panic!("Unexpected error: {e:?}"),
+        }
+
+        let source = source.replace(
+            r#"{"type": "Output"}"#,
+            r#"{"name": "d", "type": "Output"}"#,
+        );
+        let value = parse_json(&source).unwrap();
+        let error = deserialize_value::<PywrNetwork>(&source, value)
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            "missing field `type` at `parameters.p2` (line 8, column 15)"
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_location() {
+        let error = parse_json("{\n    \"nodes\": [}\n}").err().unwrap();
+        match error {
+            PywrSchemaError::DeserializationError {
+                path, line, column, ..
+            } => {
+                assert_eq!(path, "");
+                assert_eq!((line, column), (2, 15));
+            }
+            e => panic!("Unexpected error: {e:?}"),
+        }
+    }
+
+    /// Columns are counted in bytes for both syntax and deserialization errors.
+    #[test]
+    fn test_non_ascii_error_location() {
+        let source = r#"{"comment": "débit", "nodes": [{"name": "a"}]}"#;
+        let value = parse_json(source).unwrap();
+        match deserialize_value::<PywrNetwork>(source, value) {
+            Err(PywrSchemaError::DeserializationError { line, column, .. }) => {
+                assert_eq!((line, column), (1, source.find(r#"{"name""#).unwrap() + 1));
+            }
+            _ => panic!("Expected a deserialization error."),
+        }
+
+        let source = r#"{"comment": "débit", "nodes": [}"#;
+        match parse_json(source) {
+            Err(PywrSchemaError::DeserializationError { line, column, .. }) => {
+                assert_eq!((line, column), (1, source.find('}').unwrap() + 1));
+            }
+            _ => panic!("Expected a deserialization error."),
+        }
+    }
+
+    /// Errors within the network of a model are reported with their path, even though the
+    /// network is flattened into the model.
+    #[test]
+    fn test_model_error_location() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+
+        let model = r#"{
+    "metadata": {"title": "Error location"},
+    "timestepper": {"start": "2015-01-01", "end": "2015-12-31", "timestep": 1},
+    "nodes": [{"name": "supply1", "type": "Input"}],
+    "edges": [["supply1"]]
+}"#;
+        fs::write(dir.join("model.json"), model).unwrap();
+
+        match PywrModel::from_path(dir.join("model.json")) {
+            Err(PywrSchemaError::DeserializationError {
+                path, line, column, ..
+            }) => {
+                assert_eq!(path, "edges[0]");
+                assert_eq!((line, column), (5, 15));
+            }
+            _ => panic!("Expected a deserialization error."),
+        }
+
+        let model = model
+            .replace(r#"[["supply1"]]"#, "[]")
+            .replace(r#""timestep": 1"#, r#""timestep": true"#);
+        fs::write(dir.join("model.json"), model).unwrap();
+
+        match PywrModel::from_path(dir.join("model.json")) {
+            Err(PywrSchemaError::DeserializationError { path, line, .. }) => {
+                assert_eq!(path, "timestepper.timestep");
+                assert_eq!(line, 3);
+            }
+            _ => panic!("Expected a deserialization error."),
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::PywrSchemaError;
+use crate::merge::{Keep, merge_models};
+use crate::model::PywrModel;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A [`PywrModel`] that remembers the JSON it was loaded from, so that it can be saved
+/// without losing information.
+///
+/// Deserializing a [`PywrModel`] normalises the JSON: attributes that are not part of the
+/// schema are dropped, `type` strings are replaced by their canonical spelling, integers
+/// become floats and optional attributes may be written with their default values. When a
+/// `LosslessModel` is serialized, only the parts of `model` that have changed since it was
+/// loaded are written from the model; everything else is written exactly as it was loaded.
+///
+/// A component that is removed from the model is removed from the output along with any
+/// attributes the schema does not know about.
+#[derive(Clone)]
+pub struct LosslessModel {
+    pub model: PywrModel,
+    /// The JSON the model was loaded from.
+    original: Value,
+    /// The serialized form of the model when it was loaded.
+    loaded: Value,
+}
+
+impl FromStr for LosslessModel {
+    type Err = PywrSchemaError;
+
+    /// Load a model from a JSON string.
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        Self::from_value(serde_json::from_str(data)?)
+    }
+}
+
+impl LosslessModel {
+    /// Load a model from a file path.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, PywrSchemaError> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// Load a model from a JSON value.
+    pub fn from_value(original: Value) -> Result<Self, PywrSchemaError> {
+        let model: PywrModel = serde_json::from_value(original.clone())?;
+        let loaded = serde_json::to_value(&model)?;
+
+        Ok(Self {
+            model,
+            original,
+            loaded,
+        })
+    }
+
+    /// Return the JSON representation of the model, preserving the original JSON of any
+    /// unchanged parts of the model.
+    pub fn to_value(&self) -> Result<Value, PywrSchemaError> {
+        let current = serde_json::to_value(&self.model)?;
+        // Changes made to the model since it was loaded take precedence over the original
+        // representation of the same attribute.
+        let (mut merged, _) = merge_models(&self.loaded, &self.original, &current, Keep::Theirs);
+
+        // Keep any top-level keys that are not part of the schema, or are not written by the
+        // merge because they are empty, unless they have been changed.
+        if let Value::Object(original) = &self.original {
+            for (key, value) in original {
+                if !merged.contains_key(key) && current.get(key) == self.loaded.get(key) {
+                    merged.insert(key.clone(), value.clone());
+                }
+            }
+        }
+
+        Ok(Value::Object(merged))
+    }
+}
+
+impl Serialize for LosslessModel {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::Error;
+
+        self.to_value()
+            .map_err(|e| S::Error::custom(e.to_string()))?
+            .serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LosslessModel;
+    use crate::nodes::{CoreNode, Node};
+    use crate::parameters::ParameterValue;
+    use serde_json::{Value, json};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn test_model_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("models")
+    }
+
+    /// Loading and saving each of the test models should give identical JSON.
+    #[test]
+    fn test_round_trip_is_lossless() {
+        for entry in fs::read_dir(test_model_dir()).unwrap() {
+            let path = entry.unwrap().path();
+            // `extra2.json` is a network included by `extra1.json` rather than a model.
+            if path.extension().is_none_or(|ext| ext != "json") || path.ends_with("extra2.json") {
+                continue;
+            }
+
+            let original: Value =
+                serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+            let model = LosslessModel::from_value(original.clone()).unwrap();
+            let output = serde_json::to_value(&model).unwrap();
+
+            assert_eq!(output, original, "Round trip of {path:?} is not lossless");
+        }
+    }
+
+    #[test]
+    fn test_changes_are_saved() {
+        let path = test_model_dir().join("parameter_reference.json");
+        let original: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let mut model = LosslessModel::from_value(original.clone()).unwrap();
+
+        // Replace the cost of `demand1` with a constant, and remove the parameter it used.
+        let Some(Node::Core(node)) = model.model.network.nodes.as_mut().unwrap().get_mut(2) else {
+            panic!("Expected `demand1` to be a core node");
+        };
+        let CoreNode::Output(demand) = node.as_mut() else {
+            panic!("Expected `demand1` to be an output node");
+        };
+        demand.cost = Some(ParameterValue::Constant(-5.0));
+        model
+            .model
+            .network
+            .parameters
+            .as_mut()
+            .unwrap()
+            .retain(|p| p.name() != Some("demand_cost"));
+
+        // The rest of the model, such as the `values` alias and integer flows, is unchanged.
+        let mut expected = original;
+        expected["nodes"][2]["cost"] = json!(-5.0);
+        expected["parameters"]
+            .as_object_mut()
+            .unwrap()
+            .remove("demand_cost");
+
+        assert_eq!(model.to_value().unwrap(), expected);
+    }
+}
@@ -0,0 +1,465 @@
+use crate::PywrSchemaError;
+use crate::diff::DiffComponent;
+use crate::json_path::{JsonPath, PathSegment};
+use crate::model::PywrModel;
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+/// An attribute that was changed differently in both models being merged.
+///
+/// `attribute` is empty if the conflict is over the whole component (e.g. it was removed
+/// in one model and changed in the other). `base`, `ours` and `theirs` are `None` where the
+/// attribute or component does not exist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+    pub component: DiffComponent,
+    pub attribute: JsonPath,
+    pub base: Option<Value>,
+    pub ours: Option<Value>,
+    pub theirs: Option<Value>,
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let display = |value: &Option<Value>| match value {
+            Some(value) => value.to_string(),
+            None => "(none)".to_string(),
+        };
+
+        match self.attribute.is_empty() {
+            true => write!(f, "{}", self.component)?,
+            false => write!(f, "{} (attribute `{}`)", self.component, self.attribute)?,
+        }
+
+        write!(
+            f,
+            ": base {}, ours {}, theirs {}",
+            display(&self.base),
+            display(&self.ours),
+            display(&self.theirs)
+        )
+    }
+}
+
+/// The result of [`PywrModel::merge`].
+pub struct MergeResult {
+    /// The merged model. Conflicting attributes take the value from our model.
+    pub model: PywrModel,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl MergeResult {
+    /// Return true if the models were merged without any conflicts.
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+impl PywrModel {
+    /// Merge the changes made to `base` in `ours` and `theirs`.
+    ///
+    /// Scenarios, nodes, parameters, tables and recorders are matched by name, and merged
+    /// attribute by attribute. A change made in only one of the models is applied to the
+    /// merged model, and changes to different attributes of the same component are combined.
+    /// If both models change the same attribute differently this is reported as a conflict,
+    /// and our value is kept. Lists of values (e.g. the `parameters` of an aggregated
+    /// parameter), including the scenario combinations, are merged as a single attribute.
+    /// Edges and includes are added or removed if either model adds or removes them.
+    ///
+    /// Components are ordered as in our model, followed by those only in their model. An
+    /// error is returned if the merged components do not form a valid model (e.g. a
+    /// parameter's type was changed in one model and its attributes in the other).
+    pub fn merge(
+        base: &PywrModel,
+        ours: &PywrModel,
+        theirs: &PywrModel,
+    ) -> Result<MergeResult, PywrSchemaError> {
+        let base = serde_json::to_value(base)?;
+        let ours = serde_json::to_value(ours)?;
+        let theirs = serde_json::to_value(theirs)?;
+
+        let (merged, conflicts) = merge_models(&base, &ours, &theirs, Keep::Ours);
+        let model = serde_json::from_value(Value::Object(merged))?;
+
+        Ok(MergeResult { model, conflicts })
+    }
+}
+
+/// Which value to keep when an attribute is changed differently in both models.
+#[derive(Clone, Copy)]
+pub(crate) enum Keep {
+    Ours,
+    Theirs,
+}
+
+/// Merge the JSON representations of three models, returning the merged JSON object and any
+/// conflicts. Only the keys of a [`PywrModel`] are merged.
+pub(crate) fn merge_models(
+    base: &Value,
+    ours: &Value,
+    theirs: &Value,
+    keep: Keep,
+) -> (Map<String, Value>, Vec<MergeConflict>) {
+    let mut merger = Merger {
+        base,
+        ours,
+        theirs,
+        keep,
+        conflicts: Vec::new(),
+    };
+    let mut merged = Map::new();
+
+    for (key, component) in [
+        ("metadata", DiffComponent::Metadata),
+        ("timestepper", DiffComponent::Timestepper),
+        ("scenario_combinations", DiffComponent::ScenarioCombinations),
+    ] {
+        let value =
+            merger.merge_component(component, base.get(key), ours.get(key), theirs.get(key));
+        if let Some(value) = value {
+            merged.insert(key.to_string(), value);
+        }
+    }
+
+    merger.merge_list(&mut merged, "scenarios", DiffComponent::Scenario);
+    merger.merge_set(&mut merged, "includes");
+    merger.merge_list(&mut merged, "nodes", DiffComponent::Node);
+    merger.merge_set(&mut merged, "edges");
+    merger.merge_map(&mut merged, "parameters", DiffComponent::Parameter);
+    merger.merge_map(&mut merged, "tables", DiffComponent::Table);
+    merger.merge_map(&mut merged, "recorders", DiffComponent::Recorder);
+
+    (merged, merger.conflicts)
+}
+
+struct Merger<'a> {
+    base: &'a Value,
+    ours: &'a Value,
+    theirs: &'a Value,
+    keep: Keep,
+    conflicts: Vec<MergeConflict>,
+}
+
+impl<'a> Merger<'a> {
+    /// Merge a list of named components (e.g. `nodes`), identified by their `name` attribute.
+    fn merge_list<F>(&mut self, merged: &mut Map<String, Value>, key: &str, component: F)
+    where
+        F: Fn(String) -> DiffComponent,
+    {
+        let named = |model: &'a Value| -> Vec<(String, &'a Value)> {
+            model
+                .get(key)
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .map(|v| (v["name"].as_str().unwrap_or_default().to_string(), v))
+                .collect()
+        };
+
+        let items = self.merge_named(
+            named(self.base),
+            named(self.ours),
+            named(self.theirs),
+            component,
+        );
+        if !items.is_empty() {
+            let items = items.into_iter().map(|(_, v)| v).collect();
+            merged.insert(key.to_string(), Value::Array(items));
+        }
+    }
+
+    /// Merge a map of components (e.g. `parameters`) keyed by name.
+    fn merge_map<F>(&mut self, merged: &mut Map<String, Value>, key: &str, component: F)
+    where
+        F: Fn(String) -> DiffComponent,
+    {
+        let named = |model: &'a Value| -> Vec<(String, &'a Value)> {
+            model
+                .get(key)
+                .and_then(|v| v.as_object())
+                .into_iter()
+                .flatten()
+                .map(|(name, v)| (name.clone(), v))
+                .collect()
+        };
+
+        let items = self.merge_named(
+            named(self.base),
+            named(self.ours),
+            named(self.theirs),
+            component,
+        );
+        if !items.is_empty() {
+            merged.insert(key.to_string(), Value::Object(items.into_iter().collect()));
+        }
+    }
+
+    fn merge_named<F>(
+        &mut self,
+        base: Vec<(String, &Value)>,
+        ours: Vec<(String, &Value)>,
+        theirs: Vec<(String, &Value)>,
+        component: F,
+    ) -> Vec<(String, Value)>
+    where
+        F: Fn(String) -> DiffComponent,
+    {
+        let base: HashMap<_, _> = base.into_iter().rev().collect();
+        let our_values: HashMap<_, _> = ours.iter().rev().cloned().collect();
+        let their_values: HashMap<_, _> = theirs.iter().rev().cloned().collect();
+
+        let mut seen = HashSet::new();
+        let mut merged = Vec::new();
+
+        for (name, _) in ours.iter().chain(&theirs) {
+            if !seen.insert(name.as_str()) {
+                continue;
+            }
+
+            let value = self.merge_component(
+                component(name.clone()),
+                base.get(name).copied(),
+                our_values.get(name).copied(),
+                their_values.get(name).copied(),
+            );
+            if let Some(value) = value {
+                merged.push((name.clone(), value));
+            }
+        }
+
+        merged
+    }
+
+    /// Merge a list of values without attributes (e.g. `edges`), which are kept if they are
+    /// in both models or were added by either model.
+    fn merge_set(&mut self, merged: &mut Map<String, Value>, key: &str) {
+        let values = |model: &'a Value| -> Vec<&'a Value> {
+            model
+                .get(key)
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .collect()
+        };
+        let keys = |values: &[&Value]| -> HashSet<String> {
+            values.iter().map(|v| v.to_string()).collect()
+        };
+
+        let base = keys(&values(self.base));
+        let ours = values(self.ours);
+        let theirs = values(self.theirs);
+        let (our_keys, their_keys) = (keys(&ours), keys(&theirs));
+
+        let mut seen = HashSet::new();
+        let items: Vec<Value> = ours
+            .into_iter()
+            .chain(theirs)
+            .filter(|v| {
+                let key = v.to_string();
+                let keep = match (our_keys.contains(&key), their_keys.contains(&key)) {
+                    (true, true) => true,
+                    (true, false) | (false, true) => !base.contains(&key),
+                    (false, false) => false,
+                };
+                keep && seen.insert(key)
+            })
+            .cloned()
+            .collect();
+
+        if !items.is_empty() {
+            merged.insert(key.to_string(), Value::Array(items));
+        }
+    }
+
+    fn merge_component(
+        &mut self,
+        component: DiffComponent,
+        base: Option<&Value>,
+        ours: Option<&Value>,
+        theirs: Option<&Value>,
+    ) -> Option<Value> {
+        let mut conflicts = Vec::new();
+        let merged = merge_values(
+            &mut JsonPath::new(),
+            base,
+            ours,
+            theirs,
+            self.keep,
+            &mut conflicts,
+        );
+
+        self.conflicts.extend(
+            conflicts
+                .into_iter()
+                .map(|(attribute, base, ours, theirs)| MergeConflict {
+                    component: component.clone(),
+                    attribute,
+                    base,
+                    ours,
+                    theirs,
+                }),
+        );
+
+        merged
+    }
+}
+
+type Conflict = (JsonPath, Option<Value>, Option<Value>, Option<Value>);
+
+/// Three-way merge of the JSON values at `path`, recursing into objects that were changed in
+/// both models. Returns the merged value, or `None` if the value is removed.
+fn merge_values(
+    path: &mut JsonPath,
+    base: Option<&Value>,
+    ours: Option<&Value>,
+    theirs: Option<&Value>,
+    keep: Keep,
+    conflicts: &mut Vec<Conflict>,
+) -> Option<Value> {
+    if ours == theirs || theirs == base {
+        return ours.cloned();
+    }
+    if ours == base {
+        return theirs.cloned();
+    }
+
+    if let (Some(Value::Object(o)), Some(Value::Object(t))) = (ours, theirs) {
+        let b = match base {
+            Some(Value::Object(b)) => Some(b),
+            None => None,
+            Some(_) => return conflict(path, base, ours, theirs, keep, conflicts),
+        };
+
+        let keys: BTreeSet<&String> = o.keys().chain(t.keys()).collect();
+        let mut merged = Map::new();
+        for key in keys {
+            path.push(PathSegment::Key(key.clone()));
+            let base = b.and_then(|b| b.get(key));
+            if let Some(value) = merge_values(path, base, o.get(key), t.get(key), keep, conflicts) {
+                merged.insert(key.clone(), value);
+            }
+            path.pop();
+        }
+
+        return Some(Value::Object(merged));
+    }
+
+    conflict(path, base, ours, theirs, keep, conflicts)
+}
+
+fn conflict(
+    path: &JsonPath,
+    base: Option<&Value>,
+    ours: Option<&Value>,
+    theirs: Option<&Value>,
+    keep: Keep,
+    conflicts: &mut Vec<Conflict>,
+) -> Option<Value> {
+    conflicts.push((path.clone(), base.cloned(), ours.cloned(), theirs.cloned()));
+    match keep {
+        Keep::Ours => ours.cloned(),
+        Keep::Theirs => theirs.cloned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PywrModel;
+    use crate::diff::DiffComponent;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn load_test_model(name: &str) -> PywrModel {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("models")
+            .join(name);
+        PywrModel::from_path(path).unwrap()
+    }
+
+    fn edit(model: &PywrModel, f: impl FnOnce(&mut serde_json::Value)) -> PywrModel {
+        let mut data = serde_json::to_value(model).unwrap();
+        f(&mut data);
+        serde_json::from_value(data).unwrap()
+    }
+
+    #[test]
+    fn test_merge() {
+        let base = load_test_model("simple1.json");
+
+        let ours = edit(&base, |data| {
+            data["nodes"][0]["max_flow"] = json!(20.0);
+            data["nodes"][2]["cost"] = json!(-5.0);
+            data["timestepper"]["end"] = json!("2016-12-31");
+        });
+        let theirs = edit(&base, |data| {
+            data["nodes"][2]["max_flow"] = json!(12.0);
+            data["nodes"][2]["cost"] = json!(-20.0);
+            data["nodes"]
+                .as_array_mut()
+                .unwrap()
+                .push(json!({"name": "demand2", "type": "Output"}));
+            data["edges"]
+                .as_array_mut()
+                .unwrap()
+                .push(json!(["link1", "demand2"]));
+            data["parameters"] = json!({"p1": {"type": "constant", "value": 1.0}});
+        });
+
+        let result = PywrModel::merge(&base, &ours, &theirs).unwrap();
+        assert_eq!(
+            result
+                .conflicts
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>(),
+            vec!["node `demand1` (attribute `cost`): base -10.0, ours -5.0, theirs -20.0"]
+        );
+        assert!(!result.is_clean());
+
+        let expected = edit(&base, |data| {
+            data["nodes"][0]["max_flow"] = json!(20.0);
+            data["nodes"][2]["max_flow"] = json!(12.0);
+            data["nodes"][2]["cost"] = json!(-5.0);
+            data["nodes"]
+                .as_array_mut()
+                .unwrap()
+                .push(json!({"name": "demand2", "type": "Output"}));
+            data["edges"]
+                .as_array_mut()
+                .unwrap()
+                .push(json!(["link1", "demand2"]));
+            data["parameters"] = json!({"p1": {"type": "constant", "value": 1.0}});
+            data["timestepper"]["end"] = json!("2016-12-31");
+        });
+        assert!(expected.diff(&result.model).is_empty());
+    }
+
+    #[test]
+    fn test_merge_removed_and_changed() {
+        let base = load_test_model("simple1.json");
+
+        // Ours removes `demand1` and its edge, while theirs changes it.
+        let ours = edit(&base, |data| {
+            data["nodes"].as_array_mut().unwrap().remove(2);
+            data["edges"].as_array_mut().unwrap().remove(1);
+        });
+        let theirs = edit(&base, |data| data["nodes"][2]["cost"] = json!(-20.0));
+
+        let result = PywrModel::merge(&base, &ours, &theirs).unwrap();
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(
+            result.conflicts[0].component,
+            DiffComponent::Node("demand1".to_string())
+        );
+        assert!(result.conflicts[0].attribute.is_empty());
+        assert!(result.model.get_node_by_name("demand1").is_none());
+        assert_eq!(result.model.network.edges.as_ref().unwrap().len(), 1);
+
+        // Merging with an unchanged model gives the changed model.
+        let result = PywrModel::merge(&base, &base, &theirs).unwrap();
+        assert!(result.is_clean());
+        assert!(theirs.diff(&result.model).is_empty());
+    }
+}
@@ -1,16 +1,20 @@
 use crate::PywrSchemaError;
 use crate::edge::Edge;
+use crate::location::{deserialize_value, parse_json};
 use crate::nodes::Node;
 use crate::parameters::{Parameter, ParameterVec};
+use crate::recorders::{Recorder, RecorderVec};
 use crate::tables::TableVec;
+use crate::validation::ValidationIssue;
 use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Deserializer};
+use serde_json::{Map, Value};
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io;
+use std::fs;
 use std::path::{Path, PathBuf};
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Metadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
@@ -20,21 +24,24 @@ pub struct Metadata {
     pub minimum_version: Option<String>,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Timestep {
     Days(u64),
     Frequency(String),
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum DateType {
     Date(NaiveDate),
     DateTime(NaiveDateTime),
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Timestepper {
     pub start: DateType,
     pub end: DateType,
@@ -49,18 +56,19 @@ where
 {
     let slice: Option<Vec<Option<usize>>> = Option::deserialize(deserializer)?;
 
-    if let Some(ref vec) = slice {
-        if vec.is_empty() || vec.len() > 3 {
-            return Err(serde::de::Error::custom(
-                "A scenario slice must have a length between 1 and 3 elements",
-            ));
-        }
+    if let Some(ref vec) = slice
+        && (vec.is_empty() || vec.len() > 3)
+    {
+        return Err(serde::de::Error::custom(
+            "A scenario slice must have a length between 1 and 3 elements",
+        ));
     }
 
     Ok(slice)
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Scenario {
     pub name: String,
     pub size: usize,
@@ -71,8 +79,11 @@ pub struct Scenario {
     pub ensemble_names: Option<Vec<String>>,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PywrNetwork {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub includes: Option<Vec<PathBuf>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nodes: Option<Vec<Node>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -82,18 +93,29 @@ pub struct PywrNetwork {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tables: Option<TableVec>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub recorders: Option<serde_json::Value>,
+    pub recorders: Option<RecorderVec>,
 }
 
 impl PywrNetwork {
     /// Load a PywrNetwork from a file path
+    ///
+    /// If the file cannot be deserialized the error gives the path and position of the
+    /// value that failed.
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, PywrSchemaError> {
-        let file = File::open(path)?;
-        let reader = io::BufReader::new(file);
-        // Read the JSON contents of the file as an instance of `User`.
-        let data = serde_json::from_reader(reader)?;
+        let source = fs::read_to_string(path)?;
+        let value = parse_json(&source)?;
+
+        deserialize_value(&source, value)
+    }
+
+    /// Load a PywrNetwork from a file path and merge in any included JSON files.
+    ///
+    /// See [`PywrNetwork::load_includes`] for details of how includes are resolved.
+    pub fn from_path_with_includes<P: AsRef<Path>>(path: P) -> Result<Self, PywrSchemaError> {
+        let mut network = Self::from_path(&path)?;
+        network.load_includes(path)?;
 
-        Ok(data)
+        Ok(network)
     }
 
     /// Return a [`Node`] from its name. If no node  with that name exists return [`None`].
@@ -145,6 +167,31 @@ impl PywrNetwork {
         }
     }
 
+    /// Return a [`Recorder`] from its name. If no recorder with that name exists return [`None`].
+    pub fn get_recorder_by_name(&self, name: &str) -> Option<&Recorder> {
+        match &self.recorders {
+            Some(recorders) => recorders.iter().find(|r| r.name() == name),
+            None => None,
+        }
+    }
+
+    /// Return a recorder's index from its name. If no recorder with that name exists
+    /// return [`None`].
+    pub fn get_recorder_index_by_name(&self, name: &str) -> Option<usize> {
+        match &self.recorders {
+            Some(recorders) => recorders.iter().position(|r| r.name() == name),
+            None => None,
+        }
+    }
+
+    /// Return a [`Recorder`] from its index. If no recorder with that index exists return [`None`].
+    pub fn get_recorder(&self, idx: usize) -> Option<&Recorder> {
+        match &self.recorders {
+            Some(recorders) => recorders.get(idx),
+            None => None,
+        }
+    }
+
     /// Return all of the model's resource paths
     pub fn resource_paths(&self) -> HashSet<PathBuf> {
         let mut resource_paths = HashSet::new();
@@ -152,6 +199,8 @@ impl PywrNetwork {
         resource_paths.extend(self.node_resource_paths());
         resource_paths.extend(self.parameter_resource_paths());
         resource_paths.extend(self.table_resource_paths());
+        resource_paths.extend(self.recorder_resource_paths());
+        resource_paths.extend(self.include_resource_paths());
 
         resource_paths
     }
@@ -200,8 +249,43 @@ impl PywrNetwork {
         resource_paths
     }
 
+    /// Return the model's recorder resource paths
+    ///
+    /// Note that this does not include any output files written by the recorders.
+    pub fn recorder_resource_paths(&self) -> HashSet<PathBuf> {
+        let mut resource_paths = HashSet::new();
+
+        if let Some(recorders) = &self.recorders {
+            for recorder in recorders.iter() {
+                let paths = recorder.resource_paths();
+                resource_paths.extend(paths);
+            }
+        }
+
+        resource_paths
+    }
+
+    /// Return the model's include resource paths
+    ///
+    /// This includes any JSON files that have not been merged with
+    /// [`PywrNetwork::load_includes`] and any non-JSON (e.g. Python) files.
+    pub fn include_resource_paths(&self) -> HashSet<PathBuf> {
+        match &self.includes {
+            Some(includes) => includes.iter().cloned().collect(),
+            None => HashSet::new(),
+        }
+    }
+
     /// Update resource paths
     pub fn update_resource_paths(&mut self, new_paths: &HashMap<PathBuf, PathBuf>) {
+        if let Some(includes) = &mut self.includes {
+            for include in includes.iter_mut() {
+                if let Some(new_path) = new_paths.get(include) {
+                    include.clone_from(new_path);
+                }
+            }
+        }
+
         if let Some(nodes) = &mut self.nodes {
             for node in nodes.iter_mut() {
                 node.update_resource_paths(new_paths);
@@ -219,28 +303,85 @@ impl PywrNetwork {
                 table.update_resource_paths(new_paths);
             }
         }
+
+        if let Some(recorders) = &mut self.recorders {
+            for recorder in recorders.iter_mut() {
+                recorder.update_resource_paths(new_paths);
+            }
+        }
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
+/// The attributes of a [`PywrNetwork`], which are flattened into a [`PywrModel`].
+const NETWORK_KEYS: [&str; 6] = [
+    "includes",
+    "nodes",
+    "edges",
+    "parameters",
+    "tables",
+    "recorders",
+];
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PywrModel {
     pub metadata: Metadata,
     pub timestepper: Timestepper,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub scenarios: Option<Vec<Scenario>>,
+    /// The combinations of scenario indices to run, with one index per scenario. If given,
+    /// these replace the product of the scenarios and their slices.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scenario_combinations: Option<Vec<Vec<usize>>>,
     #[serde(flatten)]
     pub network: PywrNetwork,
 }
 
 impl PywrModel {
-    /// Load a PywrNetwork from a file path
+    /// Load a PywrModel from a file path
+    ///
+    /// If the file cannot be deserialized the error gives the path and position of the
+    /// value that failed. A [`PywrSchemaError::MultiModelFile`] error is returned if the file
+    /// has a list of `models`, and should be loaded as a [`PywrMultiModel`].
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, PywrSchemaError> {
-        let file = File::open(path)?;
-        let reader = io::BufReader::new(file);
-        // Read the JSON contents of the file as an instance of `User`.
-        let data = serde_json::from_reader(reader)?;
+        let source = fs::read_to_string(path)?;
+        let mut value = parse_json(&source)?;
+
+        // The network is flattened into the model, which hides the path of any error within
+        // it. Deserialize the attributes of the network on their own, then the rest of the
+        // model.
+        let network = match &mut value {
+            Value::Object(object) => {
+                if object.contains_key("models") {
+                    return Err(PywrSchemaError::MultiModelFile);
+                }
+                let network: Map<String, Value> = NETWORK_KEYS
+                    .iter()
+                    .filter_map(|key| object.remove_entry(*key))
+                    .collect();
+                Some(deserialize_value::<PywrNetwork>(
+                    &source,
+                    Value::Object(network),
+                )?)
+            }
+            _ => None,
+        };
 
-        Ok(data)
+        let mut model: Self = deserialize_value(&source, value)?;
+        if let Some(network) = network {
+            model.network = network;
+        }
+        Ok(model)
+    }
+
+    /// Load a PywrModel from a file path and merge in any included JSON files.
+    ///
+    /// See [`PywrNetwork::load_includes`] for details of how includes are resolved.
+    pub fn from_path_with_includes<P: AsRef<Path>>(path: P) -> Result<Self, PywrSchemaError> {
+        let mut model = Self::from_path(&path)?;
+        model.network.load_includes(path)?;
+
+        Ok(model)
     }
 
     /// Return a [`Node`] from its name. If no node  with that name exists return [`None`].
@@ -274,6 +415,22 @@ impl PywrModel {
         self.network.get_parameter(idx)
     }
 
+    /// Return a [`Recorder`] from its name. If no recorder with that name exists return [`None`].
+    pub fn get_recorder_by_name(&self, name: &str) -> Option<&Recorder> {
+        self.network.get_recorder_by_name(name)
+    }
+
+    /// Return a recorder's index from its name. If no recorder with that name exists
+    /// return [`None`].
+    pub fn get_recorder_index_by_name(&self, name: &str) -> Option<usize> {
+        self.network.get_recorder_index_by_name(name)
+    }
+
+    /// Return a [`Recorder`] from its index. If no recorder with that index exists return [`None`].
+    pub fn get_recorder(&self, idx: usize) -> Option<&Recorder> {
+        self.network.get_recorder(idx)
+    }
+
     /// Return all of the model's resource paths
     pub fn resource_paths(&self) -> HashSet<PathBuf> {
         self.network.resource_paths()
@@ -296,13 +453,47 @@ impl PywrModel {
         self.network.table_resource_paths()
     }
 
+    /// Return the model's recorder resource paths
+    pub fn recorder_resource_paths(&self) -> HashSet<PathBuf> {
+        self.network.recorder_resource_paths()
+    }
+
+    /// Return the model's include resource paths
+    pub fn include_resource_paths(&self) -> HashSet<PathBuf> {
+        self.network.include_resource_paths()
+    }
+
     /// Update resource paths
     pub fn update_resource_paths(&mut self, new_paths: &HashMap<PathBuf, PathBuf>) {
         self.network.update_resource_paths(new_paths)
     }
+
+    /// Check the model's timestepper, scenarios and scenario combinations, and the references
+    /// between the components of its network.
+    ///
+    /// Each scenario must have a unique name, as many `ensemble_names` as its size (if
+    /// given), and select at least one index with its slice. Each scenario combination must
+    /// have an index for each scenario that is within the scenario's size, and duplicate
+    /// combinations are reported as warnings. Slices are ignored by Pywr if scenario
+    /// combinations are given, so this is also reported as a warning.
+    ///
+    /// The scenarios referenced by `constantscenario`, `scenariowrapper` and `dataframe`
+    /// parameters (including inline parameters) must exist, and the `values` or `parameters`
+    /// of the first two must have one entry for each member of the scenario.
+    ///
+    /// See [`Timestepper::validate`] and [`PywrNetwork::validate`] for details of the other
+    /// checks performed.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = self.timestepper.validate();
+        issues.extend(self.check_scenarios());
+        issues.extend(self.check_scenario_references());
+        issues.extend(self.network.validate());
+        issues
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SubModel {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -315,7 +506,23 @@ pub struct SubModel {
     pub solver: Option<String>,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
+impl SubModel {
+    /// Return the path of the file containing this sub-model's data, from its `filename` or
+    /// `path`.
+    pub fn source_path(&self) -> Option<&Path> {
+        self.filename.as_deref().or(self.path.as_deref())
+    }
+
+    /// Return the path of the file containing this sub-model's data, resolved relative to
+    /// the directory of the multi-model file at `path`.
+    pub fn resolve_source_path<P: AsRef<Path>>(&self, path: P) -> Option<PathBuf> {
+        let parent = path.as_ref().parent().unwrap_or_else(|| Path::new(""));
+        self.source_path().map(|p| parent.join(p))
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PywrMultiModel {
     pub metadata: Metadata,
     pub timestepper: Timestepper,
@@ -324,13 +531,32 @@ pub struct PywrMultiModel {
 
 impl PywrMultiModel {
     /// Load a PywrMultiModel from a file path
+    ///
+    /// If the file cannot be deserialized the error gives the path and position of the
+    /// value that failed.
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, PywrSchemaError> {
-        let file = File::open(path)?;
-        let reader = io::BufReader::new(file);
-        // Read the JSON contents of the file as an instance of `User`.
-        let data = serde_json::from_reader(reader)?;
+        let source = fs::read_to_string(path)?;
+        let value = parse_json(&source)?;
+
+        deserialize_value(&source, value)
+    }
+
+    /// Load a PywrMultiModel from a file path, and load the data of each sub-model that is
+    /// not given inline from its `filename` or `path`.
+    ///
+    /// Sub-model paths are resolved relative to the directory of `path`.
+    pub fn from_path_with_sub_models<P: AsRef<Path>>(path: P) -> Result<Self, PywrSchemaError> {
+        let mut multi_model = Self::from_path(&path)?;
+
+        for sub_model in &mut multi_model.models {
+            if sub_model.data.is_none()
+                && let Some(sub_model_path) = sub_model.resolve_source_path(&path)
+            {
+                sub_model.data = Some(PywrModel::from_path(sub_model_path)?);
+            }
+        }
 
-        Ok(data)
+        Ok(multi_model)
     }
 
     pub fn get_model_by_name(&self, name: &str) -> Option<&PywrModel> {
@@ -343,7 +569,8 @@ impl PywrMultiModel {
 
 #[cfg(test)]
 mod tests {
-    use crate::model::PywrModel;
+    use crate::PywrSchemaError;
+    use crate::model::{PywrModel, PywrMultiModel};
     use std::collections::{HashMap, HashSet};
     use std::ffi::{OsStr, OsString};
     use std::fs::read_dir;
@@ -422,11 +649,19 @@ mod tests {
             OsString::from("demand_saving_hdf.json"),
             vec![PathBuf::from("demand_saving_level.h5")],
         );
+        expected_resources_bv_model.insert(
+            OsString::from("extra1.json"),
+            vec![PathBuf::from("extra2.json")],
+        );
         expected_resources_bv_model.insert(
             OsString::from("multiindex_df.json"),
             vec![PathBuf::from("multiindex_data.csv")],
         );
 
+        expected_resources_bv_model.insert(
+            OsString::from("python_include.json"),
+            vec![PathBuf::from("my_parameter.py")],
+        );
         expected_resources_bv_model.insert(
             OsString::from("reservoir_initial_vol_from_table.json"),
             vec![PathBuf::from("initial_volumes.csv")],
@@ -489,7 +724,10 @@ mod tests {
         );
         expected_resources_bv_model.insert(
             OsString::from("timeseries2_with_fdc.json"),
-            vec![PathBuf::from("timeseries2.csv")],
+            vec![
+                PathBuf::from("timeseries2.csv"),
+                PathBuf::from("fdc_targets.csv"),
+            ],
         );
         expected_resources_bv_model.insert(
             OsString::from("timeseries3.json"),
@@ -526,13 +764,23 @@ mod tests {
             });
 
             // None of the standard models should have custom parameters
-            if let Some(parameters) = &model.network.parameters {
-                if parameters.iter().any(|p| p.is_custom()) {
-                    panic!(
-                        "Deserialised model ({:?}) contains unexpected custom parameters!",
-                        model_fn
-                    )
-                }
+            if let Some(parameters) = &model.network.parameters
+                && parameters.iter().any(|p| p.is_custom())
+            {
+                panic!(
+                    "Deserialised model ({:?}) contains unexpected custom parameters!",
+                    model_fn
+                )
+            }
+
+            // ... or custom recorders
+            if let Some(recorders) = &model.network.recorders
+                && recorders.iter().any(|r| r.is_custom())
+            {
+                panic!(
+                    "Deserialised model ({:?}) contains unexpected custom recorders!",
+                    model_fn
+                )
             }
 
             let found_resources = model.resource_paths();
@@ -560,6 +808,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_multi_model_sub_models() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("multi_models")
+            .join("two_models.json");
+
+        assert!(matches!(
+            PywrModel::from_path(&path),
+            Err(PywrSchemaError::MultiModelFile)
+        ));
+        assert!(matches!(
+            PywrModel::from_path_recovering(&path),
+            (None, errors) if matches!(errors.as_slice(), [PywrSchemaError::MultiModelFile])
+        ));
+
+        let multi_model = PywrMultiModel::from_path(&path).unwrap();
+        assert!(multi_model.get_model_by_name("simple").is_none());
+        assert_eq!(
+            multi_model.models[0].resolve_source_path(&path),
+            Some(path.with_file_name("../models/simple1.json"))
+        );
+
+        let multi_model = PywrMultiModel::from_path_with_sub_models(&path).unwrap();
+        let simple = multi_model.get_model_by_name("simple").unwrap();
+        assert_eq!(simple.metadata.title.as_deref(), Some("Simple 1"));
+        assert!(multi_model.get_model_by_name("dangling").is_some());
+    }
+
     // #[test]
     // fn replace_urls() {
     //     let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
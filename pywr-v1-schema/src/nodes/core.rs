@@ -5,7 +5,8 @@ use crate::parameters::{
 use pywr_v1_schema_macros::PywrNode;
 use std::collections::HashMap;
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PywrNode)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, PywrNode)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct InputNode {
     #[serde(flatten)]
     pub meta: NodeMeta,
@@ -21,9 +22,14 @@ impl InputNode {
     pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PywrNode)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, PywrNode)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct LinkNode {
     #[serde(flatten)]
     pub meta: NodeMeta,
@@ -39,9 +45,14 @@ impl LinkNode {
     pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PywrNode)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, PywrNode)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct OutputNode {
     #[serde(flatten)]
     pub meta: NodeMeta,
@@ -57,9 +68,14 @@ impl OutputNode {
     pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PywrNode)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, PywrNode)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct StorageNode {
     #[serde(flatten)]
     pub meta: NodeMeta,
@@ -79,9 +95,14 @@ impl StorageNode {
     pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PywrNode)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, PywrNode)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ReservoirNode {
     #[serde(flatten)]
     pub meta: NodeMeta,
@@ -101,9 +122,14 @@ impl ReservoirNode {
     pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PywrNode)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, PywrNode)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CatchmentNode {
     #[serde(flatten)]
     pub meta: NodeMeta,
@@ -117,9 +143,14 @@ impl CatchmentNode {
     pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PywrNode)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, PywrNode)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AggregatedNode {
     #[serde(flatten)]
     pub meta: NodeMeta,
@@ -143,9 +174,16 @@ impl AggregatedNode {
         .into_iter()
         .collect()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("nodes", self.nodes.iter_mut().collect::<Vec<&mut String>>())]
+            .into_iter()
+            .collect()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PywrNode)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, PywrNode)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AggregatedStorageNode {
     #[serde(flatten)]
     pub meta: NodeMeta,
@@ -164,4 +202,13 @@ impl AggregatedStorageNode {
         .into_iter()
         .collect()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![(
+            "storage_nodes",
+            self.storage_nodes.iter_mut().collect::<Vec<&mut String>>(),
+        )]
+        .into_iter()
+        .collect()
+    }
 }
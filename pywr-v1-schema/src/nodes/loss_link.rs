@@ -3,7 +3,8 @@ use crate::parameters::{ParameterValue, ParameterValueType, ParameterValueTypeMu
 use pywr_v1_schema_macros::PywrNode;
 use std::collections::HashMap;
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrNode)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrNode)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct LossLinkNode {
     #[serde(flatten)]
     pub meta: NodeMeta,
@@ -21,4 +22,8 @@ impl LossLinkNode {
     pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        HashMap::new()
+    }
 }
@@ -20,6 +20,7 @@ pub use delay_node::DelayNode;
 pub use loss_link::LossLinkNode;
 pub use multi_split::MultiSplitLinkNode;
 pub use piecewise_link::PiecewiseLinkNode;
+use pywr_v1_schema_macros::PywrTaggedEnum;
 pub use river_gauge::RiverGaugeNode;
 pub use river_split::RiverSplitNode;
 use serde_json::Value;
@@ -31,7 +32,8 @@ pub use virtual_storage::{
     SeasonalVirtualStorageNode, VirtualStorageNode,
 };
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct NodePosition {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schematic: Option<(f32, f32)>,
@@ -39,7 +41,8 @@ pub struct NodePosition {
     pub geographic: Option<(f32, f32)>,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct NodeMeta {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -50,7 +53,8 @@ pub struct NodeMeta {
     pub tags: Option<HashMap<String, Value>>,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CustomNode {
     #[serde(rename = "type")]
     pub ty: String,
@@ -60,7 +64,12 @@ pub struct CustomNode {
     pub attributes: HashMap<String, Value>,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, VariantNames, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, VariantNames, Clone, PywrTaggedEnum)]
+#[cfg_attr(
+    feature = "json-schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::json_schema::TypeAliases(CoreNode::TYPE_ALIASES))
+)]
 #[serde(tag = "type")]
 pub enum CoreNode {
     #[serde(alias = "input")]
@@ -172,6 +181,33 @@ impl CoreNode {
         }
     }
 
+    pub fn meta_mut(&mut self) -> &mut NodeMeta {
+        match self {
+            CoreNode::Input(n) => &mut n.meta,
+            CoreNode::Link(n) => &mut n.meta,
+            CoreNode::Output(n) => &mut n.meta,
+            CoreNode::Storage(n) => &mut n.meta,
+            CoreNode::Reservoir(n) => &mut n.meta,
+            CoreNode::Catchment(n) => &mut n.meta,
+            CoreNode::RiverGauge(n) => &mut n.meta,
+            CoreNode::LossLink(n) => &mut n.meta,
+            CoreNode::PiecewiseLink(n) => &mut n.meta,
+            CoreNode::MultiSplitLink(n) => &mut n.meta,
+            CoreNode::BreakLink(n) => &mut n.meta,
+            CoreNode::Delay(n) => &mut n.meta,
+            CoreNode::River(n) => &mut n.meta,
+            CoreNode::RiverSplit(n) => &mut n.meta,
+            CoreNode::RiverSplitWithGauge(n) => &mut n.meta,
+            CoreNode::Aggregated(n) => &mut n.meta,
+            CoreNode::AggregatedStorage(n) => &mut n.meta,
+            CoreNode::VirtualStorage(n) => &mut n.meta,
+            CoreNode::AnnualVirtualStorage(n) => &mut n.meta,
+            CoreNode::MonthlyVirtualStorage(n) => &mut n.meta,
+            CoreNode::SeasonalVirtualStorage(n) => &mut n.meta,
+            CoreNode::RollingVirtualStorage(n) => &mut n.meta,
+        }
+    }
+
     pub fn parameters(&self) -> HashMap<&str, ParameterValueType<'_>> {
         match self {
             CoreNode::Input(n) => n.parameters(),
@@ -253,6 +289,62 @@ impl CoreNode {
         }
     }
 
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        match self {
+            CoreNode::Input(n) => n.node_references_mut(),
+            CoreNode::Link(n) => n.node_references_mut(),
+            CoreNode::Output(n) => n.node_references_mut(),
+            CoreNode::Storage(n) => n.node_references_mut(),
+            CoreNode::Reservoir(n) => n.node_references_mut(),
+            CoreNode::Catchment(n) => n.node_references_mut(),
+            CoreNode::RiverGauge(n) => n.node_references_mut(),
+            CoreNode::LossLink(n) => n.node_references_mut(),
+            CoreNode::PiecewiseLink(n) => n.node_references_mut(),
+            CoreNode::MultiSplitLink(n) => n.node_references_mut(),
+            CoreNode::BreakLink(n) => n.node_references_mut(),
+            CoreNode::Delay(n) => n.node_references_mut(),
+            CoreNode::River(n) => n.node_references_mut(),
+            CoreNode::RiverSplit(n) => n.node_references_mut(),
+            CoreNode::RiverSplitWithGauge(n) => n.node_references_mut(),
+            CoreNode::Aggregated(n) => n.node_references_mut(),
+            CoreNode::AggregatedStorage(n) => n.node_references_mut(),
+            CoreNode::VirtualStorage(n) => n.node_references_mut(),
+            CoreNode::AnnualVirtualStorage(n) => n.node_references_mut(),
+            CoreNode::MonthlyVirtualStorage(n) => n.node_references_mut(),
+            CoreNode::SeasonalVirtualStorage(n) => n.node_references_mut(),
+            CoreNode::RollingVirtualStorage(n) => n.node_references_mut(),
+        }
+    }
+
+    /// Return the suffixes of the names of the sub-nodes that Pywr creates for this node.
+    ///
+    /// Each sub-node is named after this node, followed by a space and the suffix (e.g.
+    /// `link1 Gross` for a `LossLink` named `link1`).
+    pub fn sub_node_suffixes(&self) -> Vec<String> {
+        // A piecewise link has an output and an input for each step.
+        let piecewise = |nsteps: usize| {
+            let sublinks = (0..nsteps).map(|i| format!("Sublink {i}"));
+            std::iter::once("Sub Output".to_string())
+                .chain(sublinks)
+                .collect()
+        };
+
+        match self {
+            CoreNode::LossLink(_) => ["Gross", "Net", "Output", "Aggregated"]
+                .map(String::from)
+                .to_vec(),
+            CoreNode::PiecewiseLink(n) => piecewise(n.nsteps),
+            CoreNode::MultiSplitLink(n) => {
+                piecewise(n.costs.as_ref().map(|c| c.len()).unwrap_or_default())
+            }
+            CoreNode::RiverSplit(_) => piecewise(1),
+            CoreNode::RiverGauge(_) | CoreNode::RiverSplitWithGauge(_) => piecewise(2),
+            CoreNode::BreakLink(_) => vec!["(storage)".to_string()],
+            CoreNode::Delay(_) => vec!["Output".to_string(), "Input".to_string()],
+            _ => Vec::new(),
+        }
+    }
+
     pub fn is_virtual(&self) -> bool {
         matches!(
             self,
@@ -267,7 +359,8 @@ impl CoreNode {
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Node {
     Core(Box<CoreNode>),
@@ -303,6 +396,13 @@ impl Node {
         }
     }
 
+    pub fn meta_mut(&mut self) -> &mut NodeMeta {
+        match self {
+            Node::Core(n) => n.meta_mut(),
+            Node::Custom(n) => &mut n.meta,
+        }
+    }
+
     pub fn parameters(&self) -> HashMap<&str, ParameterValueType<'_>> {
         match self {
             Node::Core(n) => n.parameters(),
@@ -317,6 +417,51 @@ impl Node {
         }
     }
 
+    /// Return a map of attribute to node references.
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        match self {
+            Node::Core(n) => n.node_references(),
+            Node::Custom(_) => HashMap::new(),
+        }
+    }
+
+    /// Return a map of attribute to mutable node references.
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        match self {
+            Node::Core(n) => n.node_references_mut(),
+            Node::Custom(_) => HashMap::new(),
+        }
+    }
+
+    /// Return true if this is a virtual node (e.g. an aggregated or virtual storage node).
+    ///
+    /// Custom nodes are assumed not to be virtual.
+    pub fn is_virtual(&self) -> bool {
+        match self {
+            Node::Core(n) => n.is_virtual(),
+            Node::Custom(_) => false,
+        }
+    }
+
+    /// Return true if `name` is the name of one of the sub-nodes that Pywr creates for this
+    /// node (see [`CoreNode::sub_node_suffixes`]).
+    ///
+    /// The sub-nodes of custom nodes are not known, so any name starting with a custom node's
+    /// name followed by a space is assumed to be one of its sub-nodes.
+    pub fn is_sub_node_name(&self, name: &str) -> bool {
+        let Some(suffix) = name
+            .strip_prefix(self.name())
+            .and_then(|s| s.strip_prefix(' '))
+        else {
+            return false;
+        };
+
+        match self {
+            Node::Core(n) => n.sub_node_suffixes().iter().any(|s| s == suffix),
+            Node::Custom(_) => true,
+        }
+    }
+
     pub fn resource_paths(&self) -> Vec<PathBuf> {
         let mut resource_paths = Vec::new();
 
@@ -4,7 +4,8 @@ use crate::parameters::{
 };
 use std::collections::HashMap;
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct MultiSplitLinkNode {
     #[serde(flatten)]
     pub meta: NodeMeta,
@@ -52,4 +53,8 @@ impl MultiSplitLinkNode {
     pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        HashMap::new()
+    }
 }
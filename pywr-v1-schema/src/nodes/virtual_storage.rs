@@ -3,7 +3,8 @@ use crate::parameters::{ParameterValue, ParameterValueType, ParameterValueTypeMu
 use pywr_v1_schema_macros::PywrNode;
 use std::collections::HashMap;
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PywrNode)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, PywrNode)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct VirtualStorageNode {
     #[serde(flatten)]
     pub meta: NodeMeta,
@@ -31,6 +32,12 @@ impl VirtualStorageNode {
         .into_iter()
         .collect()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("nodes", self.nodes.iter_mut().collect::<Vec<&mut String>>())]
+            .into_iter()
+            .collect()
+    }
 }
 
 fn default_reset_day() -> u32 {
@@ -41,7 +48,8 @@ fn default_reset_month() -> u32 {
     1
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PywrNode)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, PywrNode)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AnnualVirtualStorageNode {
     #[serde(flatten)]
     pub meta: NodeMeta,
@@ -75,6 +83,12 @@ impl AnnualVirtualStorageNode {
         .into_iter()
         .collect()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("nodes", self.nodes.iter_mut().collect::<Vec<&mut String>>())]
+            .into_iter()
+            .collect()
+    }
 }
 
 fn default_months() -> u8 {
@@ -85,7 +99,8 @@ fn default_initial_months() -> u8 {
     1
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PywrNode)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, PywrNode)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct MonthlyVirtualStorageNode {
     #[serde(flatten)]
     pub meta: NodeMeta,
@@ -119,6 +134,12 @@ impl MonthlyVirtualStorageNode {
         .into_iter()
         .collect()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("nodes", self.nodes.iter_mut().collect::<Vec<&mut String>>())]
+            .into_iter()
+            .collect()
+    }
 }
 
 fn default_end_day() -> u32 {
@@ -129,7 +150,8 @@ fn default_end_month() -> u32 {
     12
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PywrNode)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, PywrNode)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SeasonalVirtualStorageNode {
     #[serde(flatten)]
     pub meta: NodeMeta,
@@ -167,9 +189,16 @@ impl SeasonalVirtualStorageNode {
         .into_iter()
         .collect()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("nodes", self.nodes.iter_mut().collect::<Vec<&mut String>>())]
+            .into_iter()
+            .collect()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PywrNode)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, PywrNode)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct RollingVirtualStorageNode {
     #[serde(flatten)]
     pub meta: NodeMeta,
@@ -201,6 +230,12 @@ impl RollingVirtualStorageNode {
         .into_iter()
         .collect()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("nodes", self.nodes.iter_mut().collect::<Vec<&mut String>>())]
+            .into_iter()
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -5,7 +5,8 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 // TODO complete these
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum AggFunc {
     Sum,
@@ -14,7 +15,8 @@ pub enum AggFunc {
     Min,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AggregatedParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -26,9 +28,14 @@ impl AggregatedParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum IndexAggFunc {
     Sum,
@@ -39,7 +46,8 @@ pub enum IndexAggFunc {
     All,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AggregatedIndexParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -51,6 +59,10 @@ impl AggregatedIndexParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        HashMap::new()
+    }
 }
 
 #[cfg(test)]
@@ -5,7 +5,8 @@ use pywr_v1_schema_macros::PywrParameter;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ControlCurveInterpolatedParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -22,9 +23,16 @@ impl ControlCurveInterpolatedParameter {
             .into_iter()
             .collect()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        vec![("storage_node", &mut self.storage_node)]
+            .into_iter()
+            .collect()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ControlCurveIndexParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -38,9 +46,16 @@ impl ControlCurveIndexParameter {
             .into_iter()
             .collect()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        vec![("storage_node", &mut self.storage_node)]
+            .into_iter()
+            .collect()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ControlCurveParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -57,9 +72,16 @@ impl ControlCurveParameter {
             .into_iter()
             .collect()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        vec![("storage_node", &mut self.storage_node)]
+            .into_iter()
+            .collect()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ControlCurvePiecewiseInterpolatedParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -82,6 +104,12 @@ impl ControlCurvePiecewiseInterpolatedParameter {
             .into_iter()
             .collect()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        vec![("storage_node", &mut self.storage_node)]
+            .into_iter()
+            .collect()
+    }
 }
 
 #[cfg(test)]
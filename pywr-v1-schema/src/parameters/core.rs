@@ -6,7 +6,8 @@ use pywr_v1_schema_macros::PywrParameter;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ConstantParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -22,10 +23,14 @@ impl ConstantParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         HashMap::new()
     }
-}
 
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        HashMap::new()
+    }
+}
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ConstantScenarioParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -42,9 +47,14 @@ impl ConstantScenarioParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct MaxParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -56,9 +66,14 @@ impl MaxParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct NegativeParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -69,9 +84,14 @@ impl NegativeParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct MinParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -83,9 +103,14 @@ impl MinParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct NegativeMinParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -97,9 +122,14 @@ impl NegativeMinParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct NegativeMaxParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -111,9 +141,14 @@ impl NegativeMaxParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct DivisionParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -125,4 +160,8 @@ impl DivisionParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        HashMap::new()
+    }
 }
@@ -3,7 +3,8 @@ use pywr_v1_schema_macros::PywrParameter;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct DataFrameParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -31,4 +32,8 @@ impl DataFrameParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        HashMap::new()
+    }
 }
@@ -3,7 +3,8 @@ use pywr_v1_schema_macros::PywrParameter;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct FlowParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -14,4 +15,8 @@ impl FlowParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         vec![("node", self.node.as_str())].into_iter().collect()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        vec![("node", &mut self.node)].into_iter().collect()
+    }
 }
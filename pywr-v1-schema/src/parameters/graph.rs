@@ -0,0 +1,364 @@
+use crate::PywrSchemaError;
+use crate::graph::strongly_connected_components;
+use crate::model::PywrNetwork;
+use crate::parameters::{Parameter, ParameterValue, ParameterValueType};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+/// A graph of the dependencies between a network's parameters.
+///
+/// The graph contains the named parameters of the network and all inline parameters,
+/// including those defined on nodes and recorders. Inline parameters that do not have a name
+/// are identified by the path to them from their owner (e.g. `supply1.max_flow` or
+/// `my_parameter.parameters[1]`).
+///
+/// A parameter depends on the parameters it references and on its inline parameters.
+/// References to missing parameters are ignored; these are reported by
+/// [`PywrNetwork::validate`].
+pub struct ParameterGraph<'a> {
+    names: Vec<String>,
+    parameters: Vec<&'a Parameter>,
+    indices: HashMap<String, usize>,
+    dependencies: Vec<Vec<usize>>,
+    dependents: Vec<Vec<usize>>,
+}
+
+impl PywrNetwork {
+    /// Create a graph of the dependencies between this network's parameters.
+    pub fn parameter_graph(&self) -> ParameterGraph<'_> {
+        ParameterGraph::new(self)
+    }
+}
+
+/// Builds a [`ParameterGraph`], deferring the resolution of references until all
+/// parameters have been added.
+#[derive(Default)]
+struct Builder<'a> {
+    names: Vec<String>,
+    parameters: Vec<&'a Parameter>,
+    indices: HashMap<String, usize>,
+    inline: Vec<(usize, usize)>,
+    references: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Builder<'a> {
+    fn add_parameter(&mut self, name: String, parameter: &'a Parameter) -> usize {
+        let idx = self.names.len();
+        self.indices.entry(name.clone()).or_insert(idx);
+        self.names.push(name);
+        self.parameters.push(parameter);
+        idx
+    }
+
+    /// Add the dependencies of `parent` (if any) on the given values, and any inline
+    /// parameters within them. `prefix` is the path of the values' owner.
+    fn add_values(
+        &mut self,
+        parent: Option<usize>,
+        prefix: &str,
+        values: HashMap<&'a str, ParameterValueType<'a>>,
+    ) {
+        let mut values: Vec<_> = values.into_iter().collect();
+        values.sort_by_key(|(attribute, _)| *attribute);
+
+        for (attribute, value_type) in values {
+            for (i, value) in value_type.values() {
+                match value {
+                    ParameterValue::Reference(name) => {
+                        if let Some(parent) = parent {
+                            self.references.push((parent, name.as_str()));
+                        }
+                    }
+                    ParameterValue::Inline(parameter) => {
+                        let name = match (parameter.name(), i) {
+                            (Some(name), _) => name.to_string(),
+                            (None, Some(i)) => format!("{prefix}.{attribute}[{i}]"),
+                            (None, None) => format!("{prefix}.{attribute}"),
+                        };
+
+                        let idx = self.add_parameter(name.clone(), parameter);
+                        if let Some(parent) = parent {
+                            self.inline.push((parent, idx));
+                        }
+                        self.add_values(Some(idx), &name, parameter.parameters());
+                    }
+                    ParameterValue::Constant(_) | ParameterValue::Table(_) => {}
+                }
+            }
+        }
+    }
+}
+
+impl<'a> ParameterGraph<'a> {
+    fn new(network: &'a PywrNetwork) -> Self {
+        let mut builder = Builder::default();
+
+        for parameter in network.parameters.iter().flat_map(|p| p.iter()) {
+            let name = parameter.name().unwrap_or_default();
+            let idx = builder.add_parameter(name.to_string(), parameter);
+            builder.add_values(Some(idx), name, parameter.parameters());
+        }
+
+        for node in network.nodes.iter().flatten() {
+            builder.add_values(None, node.name(), node.parameters());
+        }
+
+        for recorder in network.recorders.iter().flat_map(|r| r.iter()) {
+            builder.add_values(None, recorder.name(), recorder.parameters());
+        }
+
+        let mut dependencies = vec![Vec::new(); builder.names.len()];
+        let mut dependents = vec![Vec::new(); builder.names.len()];
+
+        let references = builder
+            .references
+            .iter()
+            .filter_map(|(from, name)| builder.indices.get(*name).map(|to| (*from, *to)));
+
+        for (from, to) in builder.inline.iter().copied().chain(references) {
+            if !dependencies[from].contains(&to) {
+                dependencies[from].push(to);
+                dependents[to].push(from);
+            }
+        }
+
+        Self {
+            names: builder.names,
+            parameters: builder.parameters,
+            indices: builder.indices,
+            dependencies,
+            dependents,
+        }
+    }
+
+    /// Return the names of the parameters in the graph.
+    ///
+    /// Named parameters are given in the order they are defined in the network, each followed
+    /// by its inline parameters. These are followed by the inline parameters of nodes and
+    /// then recorders.
+    pub fn parameters(&self) -> Vec<&str> {
+        self.names.iter().map(|n| n.as_str()).collect()
+    }
+
+    /// Return the parameter with the given name.
+    pub fn get_parameter(&self, name: &str) -> Option<&'a Parameter> {
+        self.indices.get(name).map(|idx| self.parameters[*idx])
+    }
+
+    /// Return the names of the parameters that the given parameter directly depends on, or
+    /// `None` if the parameter is not in the graph.
+    pub fn dependencies(&self, name: &str) -> Option<Vec<&str>> {
+        let idx = self.indices.get(name)?;
+        Some(self.to_names(&self.dependencies[*idx]))
+    }
+
+    /// Return the names of the parameters that directly depend on the given parameter, or
+    /// `None` if the parameter is not in the graph.
+    pub fn dependents(&self, name: &str) -> Option<Vec<&str>> {
+        let idx = self.indices.get(name)?;
+        Some(self.to_names(&self.dependents[*idx]))
+    }
+
+    /// Return the circular references between parameters.
+    ///
+    /// One cycle is given for each group of parameters that depend on each other (i.e. each
+    /// strongly connected component with more than one parameter, or a parameter that
+    /// references itself). Each cycle is the shortest chain of parameter names that starts
+    /// and ends with the first parameter of the group (e.g. `["a", "b", "a"]` if `a` and `b`
+    /// reference each other).
+    pub fn cycles(&self) -> Vec<Vec<&str>> {
+        let mut components: Vec<Vec<usize>> = strongly_connected_components(&self.dependencies)
+            .into_iter()
+            .filter(|c| c.len() > 1 || self.dependencies[c[0]].contains(&c[0]))
+            .collect();
+        for component in &mut components {
+            component.sort_unstable();
+        }
+        components.sort_unstable_by_key(|c| c[0]);
+
+        let mut in_component = vec![false; self.names.len()];
+        components
+            .iter()
+            .filter_map(|component| {
+                for idx in component {
+                    in_component[*idx] = true;
+                }
+                let chain = self.shortest_cycle(component[0], &in_component);
+                for idx in component {
+                    in_component[*idx] = false;
+                }
+                chain.map(|chain| self.to_names(&chain))
+            })
+            .collect()
+    }
+
+    /// Return the parameters ordered such that every parameter comes after the parameters it
+    /// depends on.
+    ///
+    /// Where the order is not constrained by a dependency, parameters are given in the order
+    /// of [`ParameterGraph::parameters`]. An error is returned if the parameters contain a
+    /// circular reference.
+    pub fn topological_order(&self) -> Result<Vec<&str>, PywrSchemaError> {
+        let mut remaining: Vec<usize> = self.dependencies.iter().map(|d| d.len()).collect();
+        let mut ready: BinaryHeap<Reverse<usize>> = (0..self.names.len())
+            .filter(|idx| remaining[*idx] == 0)
+            .map(Reverse)
+            .collect();
+        let mut order = Vec::with_capacity(self.names.len());
+
+        while let Some(Reverse(idx)) = ready.pop() {
+            order.push(idx);
+            for dependent in &self.dependents[idx] {
+                remaining[*dependent] -= 1;
+                if remaining[*dependent] == 0 {
+                    ready.push(Reverse(*dependent));
+                }
+            }
+        }
+
+        if order.len() < self.names.len() {
+            let chain = self.cycles().into_iter().next().unwrap_or_default();
+            return Err(PywrSchemaError::CircularParameterReference(
+                chain.into_iter().map(|n| n.to_string()).collect(),
+            ));
+        }
+
+        Ok(self.to_names(&order))
+    }
+
+    fn to_names(&self, indices: &[usize]) -> Vec<&str> {
+        indices
+            .iter()
+            .map(|idx| self.names[*idx].as_str())
+            .collect()
+    }
+
+    /// Breadth-first search for the shortest chain of dependencies from `start` back to
+    /// itself, following only the parameters in `component`.
+    fn shortest_cycle(&self, start: usize, component: &[bool]) -> Option<Vec<usize>> {
+        let mut previous = vec![None; self.names.len()];
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(idx) = queue.pop_front() {
+            for next in &self.dependencies[idx] {
+                if *next == start {
+                    let mut chain = vec![start];
+                    let mut current = idx;
+                    while current != start {
+                        chain.push(current);
+                        current = previous[current]?;
+                    }
+                    chain.push(start);
+                    // The path was followed backwards from the end of the chain.
+                    let len = chain.len();
+                    chain[1..len - 1].reverse();
+                    return Some(chain);
+                }
+
+                if component[*next] && previous[*next].is_none() {
+                    previous[*next] = Some(idx);
+                    queue.push_back(*next);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PywrNetwork, PywrSchemaError};
+
+    #[test]
+    fn test_parameter_graph() {
+        let data = r#"
+            {
+                "nodes": [
+                    {"name": "supply1", "type": "Input", "max_flow": {"type": "max", "parameter": "p2"}}
+                ],
+                "parameters": {
+                    "p1": {
+                        "type": "aggregated",
+                        "agg_func": "sum",
+                        "parameters": ["p2", {"type": "negative", "parameter": "p3"}]
+                    },
+                    "p2": {"type": "constant", "value": 1.0},
+                    "p3": {"type": "constant", "value": 2.0}
+                }
+            }
+            "#;
+        let network: PywrNetwork = serde_json::from_str(data).unwrap();
+        let graph = network.parameter_graph();
+
+        assert_eq!(
+            graph.parameters(),
+            vec!["p1", "p1.parameters[1]", "p2", "p3", "supply1.max_flow"]
+        );
+        assert_eq!(
+            graph.dependencies("p1"),
+            Some(vec!["p1.parameters[1]", "p2"])
+        );
+        assert_eq!(graph.dependents("p2"), Some(vec!["p1", "supply1.max_flow"]));
+        assert!(graph.cycles().is_empty());
+        assert_eq!(
+            graph.topological_order().unwrap(),
+            vec!["p2", "p3", "p1.parameters[1]", "p1", "supply1.max_flow"]
+        );
+    }
+
+    #[test]
+    fn test_parameter_cycles() {
+        let data = r#"
+            {
+                "parameters": {
+                    "p1": {"type": "max", "parameter": "p2"},
+                    "p2": {
+                        "type": "aggregated",
+                        "agg_func": "sum",
+                        "parameters": [{"type": "negative", "parameter": "p1"}]
+                    },
+                    "p3": {"type": "min", "parameter": "p3"}
+                }
+            }
+            "#;
+        let network: PywrNetwork = serde_json::from_str(data).unwrap();
+        let graph = network.parameter_graph();
+
+        assert_eq!(
+            graph.cycles(),
+            vec![vec!["p1", "p2", "p2.parameters[0]", "p1"], vec!["p3", "p3"]]
+        );
+
+        match graph.topological_order() {
+            Err(PywrSchemaError::CircularParameterReference(chain)) => {
+                assert_eq!(chain, vec!["p1", "p2", "p2.parameters[0]", "p1"])
+            }
+            _ => panic!("Expected a circular parameter reference error."),
+        }
+    }
+
+    #[test]
+    fn test_overlapping_parameter_cycles() {
+        // `p1` and `p2`, and `p2` and `p3`, reference each other, which is reported once.
+        let data = r#"
+            {
+                "parameters": {
+                    "p1": {"type": "max", "parameter": "p2"},
+                    "p2": {
+                        "type": "aggregated",
+                        "agg_func": "sum",
+                        "parameters": ["p1", "p3"]
+                    },
+                    "p3": {"type": "min", "parameter": "p2"}
+                }
+            }
+            "#;
+        let network: PywrNetwork = serde_json::from_str(data).unwrap();
+
+        assert_eq!(
+            network.parameter_graph().cycles(),
+            vec![vec!["p1", "p2", "p1"]]
+        );
+    }
+}
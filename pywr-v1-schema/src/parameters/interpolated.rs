@@ -5,7 +5,8 @@ use pywr_v1_schema_macros::PywrParameter;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct InterpolatedVolumeParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -20,9 +21,14 @@ impl InterpolatedVolumeParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         vec![("node", self.node.as_str())].into_iter().collect()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        vec![("node", &mut self.node)].into_iter().collect()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct InterpolatedFlowParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -37,4 +43,8 @@ impl InterpolatedFlowParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         vec![("node", self.node.as_str())].into_iter().collect()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        vec![("node", &mut self.node)].into_iter().collect()
+    }
 }
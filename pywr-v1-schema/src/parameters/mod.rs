@@ -6,6 +6,7 @@ mod data_frame;
 mod deficit;
 mod discount_factor;
 mod flow;
+mod graph;
 mod hydropower;
 mod indexed_array;
 mod interpolated;
@@ -33,6 +34,7 @@ pub use crate::parameters::core::{
 pub use crate::parameters::deficit::DeficitParameter;
 pub use crate::parameters::discount_factor::DiscountFactorParameter;
 pub use crate::parameters::flow::FlowParameter;
+pub use crate::parameters::graph::ParameterGraph;
 pub use crate::parameters::hydropower::HydropowerTargetParameter;
 pub use crate::parameters::indexed_array::IndexedArrayParameter;
 pub use crate::parameters::interpolated::{InterpolatedFlowParameter, InterpolatedVolumeParameter};
@@ -51,6 +53,7 @@ pub use crate::parameters::thresholds::{
     NodeThresholdParameter, ParameterThresholdParameter, Predicate, StorageThresholdParameter,
 };
 pub use data_frame::DataFrameParameter;
+use pywr_v1_schema_macros::PywrTaggedEnum;
 use serde::de::value::MapDeserializer;
 use serde::de::{MapAccess, Visitor};
 use serde::ser::{Error, SerializeMap};
@@ -63,7 +66,8 @@ use std::ops::{Deref, DerefMut};
 use std::path::PathBuf;
 use std::vec::IntoIter;
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ParameterMeta {
     // Do not serialize name on the object as it is used as the key in the parent map,
     // and we don't want to duplicate it in the output.
@@ -77,7 +81,8 @@ pub struct ParameterMeta {
     pub tags: Option<HashMap<String, Value>>,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CustomParameter {
     #[serde(rename = "type")]
     pub ty: String,
@@ -91,7 +96,12 @@ pub struct CustomParameter {
 // Issues:
 //   - https://github.com/serde-rs/serde/pull/1902
 //   - https://github.com/serde-rs/serde/pull/2161
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrTaggedEnum)]
+#[cfg_attr(
+    feature = "json-schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::json_schema::TypeAliases(CoreParameter::TYPE_ALIASES))
+)]
 #[serde(tag = "type")]
 pub enum CoreParameter {
     #[serde(
@@ -374,6 +384,51 @@ impl CoreParameter {
         }
     }
 
+    fn meta_mut(&mut self) -> &mut Option<ParameterMeta> {
+        match self {
+            Self::Constant(p) => &mut p.meta,
+            Self::ConstantScenario(p) => &mut p.meta,
+            Self::ControlCurveInterpolated(p) => &mut p.meta,
+            Self::Aggregated(p) => &mut p.meta,
+            Self::AggregatedIndex(p) => &mut p.meta,
+            Self::AsymmetricSwitchIndex(p) => &mut p.meta,
+            Self::ControlCurvePiecewiseInterpolated(p) => &mut p.meta,
+            Self::ControlCurveIndex(p) => &mut p.meta,
+            Self::ControlCurve(p) => &mut p.meta,
+            Self::DailyProfile(p) => &mut p.meta,
+            Self::IndexedArray(p) => &mut p.meta,
+            Self::MonthlyProfile(p) => &mut p.meta,
+            Self::WeeklyProfile(p) => &mut p.meta,
+            Self::UniformDrawdownProfile(p) => &mut p.meta,
+            Self::Max(p) => &mut p.meta,
+            Self::Min(p) => &mut p.meta,
+            Self::NegativeMin(p) => &mut p.meta,
+            Self::NegativeMax(p) => &mut p.meta,
+            Self::Division(p) => &mut p.meta,
+            Self::Negative(p) => &mut p.meta,
+            Self::Polynomial1D(p) => &mut p.meta,
+            Self::ParameterThreshold(p) => &mut p.meta,
+            Self::NodeThreshold(p) => &mut p.meta,
+            Self::StorageThreshold(p) => &mut p.meta,
+            Self::MultipleThresholdIndex(p) => &mut p.meta,
+            Self::MultipleThresholdParameterIndex(p) => &mut p.meta,
+            Self::CurrentYearThreshold(p) => &mut p.meta,
+            Self::CurrentOrdinalDayThreshold(p) => &mut p.meta,
+            Self::TablesArray(p) => &mut p.meta,
+            Self::DataFrame(p) => &mut p.meta,
+            Self::Deficit(p) => &mut p.meta,
+            Self::DiscountFactor(p) => &mut p.meta,
+            Self::InterpolatedVolume(p) => &mut p.meta,
+            Self::InterpolatedFlow(p) => &mut p.meta,
+            Self::HydropowerTarget(p) => &mut p.meta,
+            Self::Storage(p) => &mut p.meta,
+            Self::RollingMeanFlowNode(p) => &mut p.meta,
+            Self::ScenarioWrapper(p) => &mut p.meta,
+            Self::Flow(p) => &mut p.meta,
+            Self::RbfProfile(p) => &mut p.meta,
+        }
+    }
+
     fn node_references(&self) -> HashMap<&str, &str> {
         match self {
             Self::Constant(p) => p.node_references(),
@@ -419,6 +474,101 @@ impl CoreParameter {
         }
     }
 
+    fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        match self {
+            Self::Constant(p) => p.node_references_mut(),
+            Self::ConstantScenario(p) => p.node_references_mut(),
+            Self::ControlCurveInterpolated(p) => p.node_references_mut(),
+            Self::Aggregated(p) => p.node_references_mut(),
+            Self::AggregatedIndex(p) => p.node_references_mut(),
+            Self::AsymmetricSwitchIndex(p) => p.node_references_mut(),
+            Self::ControlCurvePiecewiseInterpolated(p) => p.node_references_mut(),
+            Self::ControlCurveIndex(p) => p.node_references_mut(),
+            Self::ControlCurve(p) => p.node_references_mut(),
+            Self::DailyProfile(p) => p.node_references_mut(),
+            Self::IndexedArray(p) => p.node_references_mut(),
+            Self::MonthlyProfile(p) => p.node_references_mut(),
+            Self::WeeklyProfile(p) => p.node_references_mut(),
+            Self::UniformDrawdownProfile(p) => p.node_references_mut(),
+            Self::Max(p) => p.node_references_mut(),
+            Self::NegativeMin(p) => p.node_references_mut(),
+            Self::NegativeMax(p) => p.node_references_mut(),
+            Self::Min(p) => p.node_references_mut(),
+            Self::Division(p) => p.node_references_mut(),
+            Self::Negative(p) => p.node_references_mut(),
+            Self::Polynomial1D(p) => p.node_references_mut(),
+            Self::ParameterThreshold(p) => p.node_references_mut(),
+            Self::NodeThreshold(p) => p.node_references_mut(),
+            Self::StorageThreshold(p) => p.node_references_mut(),
+            Self::MultipleThresholdIndex(p) => p.node_references_mut(),
+            Self::MultipleThresholdParameterIndex(p) => p.node_references_mut(),
+            Self::CurrentYearThreshold(p) => p.node_references_mut(),
+            Self::CurrentOrdinalDayThreshold(p) => p.node_references_mut(),
+            Self::TablesArray(p) => p.node_references_mut(),
+            Self::DataFrame(p) => p.node_references_mut(),
+            Self::Deficit(p) => p.node_references_mut(),
+            Self::DiscountFactor(p) => p.node_references_mut(),
+            Self::InterpolatedVolume(p) => p.node_references_mut(),
+            Self::InterpolatedFlow(p) => p.node_references_mut(),
+            Self::HydropowerTarget(p) => p.node_references_mut(),
+            Self::Storage(p) => p.node_references_mut(),
+            Self::RollingMeanFlowNode(p) => p.node_references_mut(),
+            Self::ScenarioWrapper(p) => p.node_references_mut(),
+            Self::Flow(p) => p.node_references_mut(),
+            Self::RbfProfile(p) => p.node_references_mut(),
+        }
+    }
+
+    fn table_references(&self) -> HashMap<&str, &str> {
+        match self {
+            Self::Constant(p) => p.table_references(),
+            Self::ConstantScenario(p) => p.table_references(),
+            Self::ControlCurveInterpolated(p) => p.table_references(),
+            Self::Aggregated(p) => p.table_references(),
+            Self::AggregatedIndex(p) => p.table_references(),
+            Self::AsymmetricSwitchIndex(p) => p.table_references(),
+            Self::ControlCurvePiecewiseInterpolated(p) => p.table_references(),
+            Self::ControlCurveIndex(p) => p.table_references(),
+            Self::ControlCurve(p) => p.table_references(),
+            Self::DailyProfile(p) => p.table_references(),
+            Self::IndexedArray(p) => p.table_references(),
+            Self::MonthlyProfile(p) => p.table_references(),
+            Self::WeeklyProfile(p) => p.table_references(),
+            Self::UniformDrawdownProfile(p) => p.table_references(),
+            Self::Max(p) => p.table_references(),
+            Self::NegativeMin(p) => p.table_references(),
+            Self::NegativeMax(p) => p.table_references(),
+            Self::Min(p) => p.table_references(),
+            Self::Division(p) => p.table_references(),
+            Self::Negative(p) => p.table_references(),
+            Self::Polynomial1D(p) => p.table_references(),
+            Self::ParameterThreshold(p) => p.table_references(),
+            Self::NodeThreshold(p) => p.table_references(),
+            Self::StorageThreshold(p) => p.table_references(),
+            Self::MultipleThresholdIndex(p) => p.table_references(),
+            Self::MultipleThresholdParameterIndex(p) => p.table_references(),
+            Self::CurrentYearThreshold(p) => p.table_references(),
+            Self::CurrentOrdinalDayThreshold(p) => p.table_references(),
+            Self::TablesArray(p) => p.table_references(),
+            // The data frame's `table` is a plain name rather than a `TableDataRef`.
+            Self::DataFrame(p) => p
+                .table
+                .iter()
+                .map(|table| ("table", table.as_str()))
+                .collect(),
+            Self::Deficit(p) => p.table_references(),
+            Self::DiscountFactor(p) => p.table_references(),
+            Self::InterpolatedVolume(p) => p.table_references(),
+            Self::InterpolatedFlow(p) => p.table_references(),
+            Self::HydropowerTarget(p) => p.table_references(),
+            Self::Storage(p) => p.table_references(),
+            Self::RollingMeanFlowNode(p) => p.table_references(),
+            Self::ScenarioWrapper(p) => p.table_references(),
+            Self::Flow(p) => p.table_references(),
+            Self::RbfProfile(p) => p.table_references(),
+        }
+    }
+
     pub fn parameters(&self) -> HashMap<&str, ParameterValueType<'_>> {
         match self {
             Self::Constant(p) => p.parameters(),
@@ -692,7 +842,10 @@ impl CoreParameter {
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+// Boxing the core variant would change the public API, so the size difference is accepted.
+#[allow(clippy::large_enum_variant)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Parameter {
     Core(CoreParameter),
@@ -718,6 +871,25 @@ impl Parameter {
         }
     }
 
+    /// Set or clear the parameter's name.
+    pub fn set_name(&mut self, name: Option<&str>) {
+        let name = name.map(|n| n.to_string());
+        match self {
+            Self::Core(p) => match p.meta_mut() {
+                Some(meta) => meta.name = name,
+                meta @ None => {
+                    *meta = Some(ParameterMeta {
+                        name,
+                        comment: None,
+                        is_variable: None,
+                        tags: None,
+                    })
+                }
+            },
+            Self::Custom(p) => p.meta.name = name,
+        }
+    }
+
     /// Return a map of attribute to node references.
     pub fn node_references(&self) -> HashMap<&str, &str> {
         match self {
@@ -726,6 +898,22 @@ impl Parameter {
         }
     }
 
+    /// Return a map of attribute to mutable node references.
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        match self {
+            Self::Core(p) => p.node_references_mut(),
+            Self::Custom(_) => HashMap::new(),
+        }
+    }
+
+    /// Return a map of attribute to table references.
+    pub fn table_references(&self) -> HashMap<&str, &str> {
+        match self {
+            Self::Core(p) => p.table_references(),
+            Self::Custom(_) => HashMap::new(),
+        }
+    }
+
     /// Return a map of attribute to parameter values.
     pub fn parameters(&self) -> HashMap<&str, ParameterValueType<'_>> {
         match self {
@@ -734,6 +922,14 @@ impl Parameter {
         }
     }
 
+    /// Return a map of attribute to mutable parameter values.
+    pub fn parameters_mut(&mut self) -> HashMap<&str, ParameterValueTypeMut<'_>> {
+        match self {
+            Self::Core(p) => p.parameters_mut(),
+            Self::Custom(_) => HashMap::new(),
+        }
+    }
+
     /// Return the type of the parameter
     pub fn ty(&self) -> &str {
         match self {
@@ -769,7 +965,7 @@ impl Parameter {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Default)]
 pub struct ParameterVec(Vec<Parameter>);
 
 impl ParameterVec {
@@ -813,7 +1009,7 @@ impl PywrParameterMapVisitor {
     }
 }
 
-fn remove_suffix<'a>(s: &'a str, suffix: &str) -> &'a str {
+pub(crate) fn remove_suffix<'a>(s: &'a str, suffix: &str) -> &'a str {
     match s.strip_suffix(suffix) {
         Some(s) => s,
         None => s,
@@ -864,16 +1060,24 @@ impl<'de> Visitor<'de> for PywrParameterMapVisitor {
                 match CoreParameter::deserialize(MapDeserializer::new(py_attributes.into_iter())) {
                     Ok(p) => Parameter::Core(p),
                     // Deserializing a core parameter failed; deserialize as a custom parameter
-                    Err(_) => Parameter::Custom(CustomParameter {
-                        meta: ParameterMeta {
-                            name: Some(name),
-                            comment: value.comment,
-                            is_variable: None,
-                            tags: value.tags,
-                        },
-                        ty: value.ty,
-                        attributes: value.attributes,
-                    }),
+                    Err(_) => {
+                        let mut attributes = value.attributes;
+                        let is_variable = attributes.get("is_variable").and_then(Value::as_bool);
+                        if is_variable.is_some() {
+                            attributes.remove("is_variable");
+                        }
+
+                        Parameter::Custom(CustomParameter {
+                            meta: ParameterMeta {
+                                name: Some(name),
+                                comment: value.comment,
+                                is_variable,
+                                tags: value.tags,
+                            },
+                            ty: value.ty,
+                            attributes,
+                        })
+                    }
                 };
 
             map.push(p);
@@ -909,7 +1113,8 @@ impl Serialize for ParameterVec {
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ParameterValue {
     Constant(f64),
@@ -927,6 +1132,27 @@ pub enum ParameterValueType<'a> {
     OptionalList(&'a OptionalParameterValues),
 }
 
+impl<'a> ParameterValueType<'a> {
+    /// Return the parameter values, along with their index if this is a list.
+    ///
+    /// Missing entries of optional lists are skipped.
+    pub fn values(&self) -> Vec<(Option<usize>, &'a ParameterValue)> {
+        match self {
+            Self::Single(value) => vec![(None, *value)],
+            Self::List(values) => values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (Some(i), v))
+                .collect(),
+            Self::OptionalList(values) => values
+                .iter()
+                .enumerate()
+                .filter_map(|(i, v)| v.as_ref().map(|v| (Some(i), v)))
+                .collect(),
+        }
+    }
+}
+
 impl<'a> From<&'a ParameterValue> for ParameterValueType<'a> {
     fn from(v: &'a ParameterValue) -> Self {
         Self::Single(v)
@@ -950,6 +1176,27 @@ pub enum ParameterValueTypeMut<'a> {
     OptionalList(&'a mut OptionalParameterValues),
 }
 
+impl<'a> ParameterValueTypeMut<'a> {
+    /// Return the parameter values, along with their index if this is a list.
+    ///
+    /// Missing entries of optional lists are skipped.
+    pub fn into_values(self) -> Vec<(Option<usize>, &'a mut ParameterValue)> {
+        match self {
+            Self::Single(value) => vec![(None, value)],
+            Self::List(values) => values
+                .iter_mut()
+                .enumerate()
+                .map(|(i, v)| (Some(i), v))
+                .collect(),
+            Self::OptionalList(values) => values
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(i, v)| v.as_mut().map(|v| (Some(i), v)))
+                .collect(),
+        }
+    }
+}
+
 impl<'a> From<&'a mut ParameterValue> for ParameterValueTypeMut<'a> {
     fn from(v: &'a mut ParameterValue) -> Self {
         Self::Single(v)
@@ -988,7 +1235,96 @@ impl ParameterValue {
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+/// Call `visitor` for each parameter value in `values`, including any values nested within
+/// inline parameters.
+///
+/// The visitor is given the path of each value's attribute relative to the owner of `values`
+/// (e.g. `max_flow`, `control_curves[1]` or `max_flow.parameters[0]`). Attributes are visited
+/// in name order so that the order of visits is deterministic.
+pub fn visit_parameter_values<'a, F>(values: HashMap<&str, ParameterValueType<'a>>, visitor: &mut F)
+where
+    F: FnMut(&str, &'a ParameterValue),
+{
+    visit_parameter_values_with_prefix("", values, visitor);
+}
+
+fn visit_parameter_values_with_prefix<'a, F>(
+    prefix: &str,
+    values: HashMap<&str, ParameterValueType<'a>>,
+    visitor: &mut F,
+) where
+    F: FnMut(&str, &'a ParameterValue),
+{
+    let mut values: Vec<_> = values.into_iter().collect();
+    values.sort_by_key(|(attribute, _)| *attribute);
+
+    for (attribute, value_type) in values {
+        for (i, value) in value_type.values() {
+            let path = match i {
+                Some(i) => format!("{prefix}{attribute}[{i}]"),
+                None => format!("{prefix}{attribute}"),
+            };
+            visit_parameter_value(path, value, visitor);
+        }
+    }
+}
+
+fn visit_parameter_value<'a, F>(path: String, value: &'a ParameterValue, visitor: &mut F)
+where
+    F: FnMut(&str, &'a ParameterValue),
+{
+    visitor(&path, value);
+
+    if let ParameterValue::Inline(parameter) = value {
+        visit_parameter_values_with_prefix(&format!("{path}."), parameter.parameters(), visitor);
+    }
+}
+
+/// Call `visitor` for each mutable parameter value in `values`, including any values nested
+/// within inline parameters.
+///
+/// This is the mutable counterpart of [`visit_parameter_values`]. Inline parameters are
+/// visited after the visitor has been called on their value, so any inline parameter
+/// that the visitor replaces is not visited.
+pub fn visit_parameter_values_mut<F>(values: HashMap<&str, ParameterValueTypeMut>, visitor: &mut F)
+where
+    F: FnMut(&str, &mut ParameterValue),
+{
+    visit_parameter_values_mut_with_prefix("", values, visitor);
+}
+
+fn visit_parameter_values_mut_with_prefix<F>(
+    prefix: &str,
+    values: HashMap<&str, ParameterValueTypeMut>,
+    visitor: &mut F,
+) where
+    F: FnMut(&str, &mut ParameterValue),
+{
+    let mut values: Vec<_> = values.into_iter().collect();
+    values.sort_by_key(|(attribute, _)| *attribute);
+
+    for (attribute, value_type) in values {
+        for (i, value) in value_type.into_values() {
+            let path = match i {
+                Some(i) => format!("{prefix}{attribute}[{i}]"),
+                None => format!("{prefix}{attribute}"),
+            };
+
+            visitor(&path, value);
+
+            if let ParameterValue::Inline(parameter) = value {
+                visit_parameter_values_mut_with_prefix(
+                    &format!("{path}."),
+                    parameter.parameters_mut(),
+                    visitor,
+                );
+            }
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ExternalDataRef {
     pub url: PathBuf,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -999,21 +1335,24 @@ pub struct ExternalDataRef {
     pub attributes: HashMap<String, Value>,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum TableIndex {
     Single(TableIndexEntry),
     Multi(Vec<TableIndexEntry>),
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum TableIndexEntry {
     Name(String),
     Index(usize),
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TableDataRef {
     pub table: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1024,7 +1363,7 @@ pub struct TableDataRef {
 
 #[cfg(test)]
 mod tests {
-    use crate::parameters::{CoreParameter, Parameter, ParameterValue};
+    use crate::parameters::{CoreParameter, Parameter, ParameterValue, ParameterVec};
 
     /// Test loading a DailyProfile with a tables definition.
     #[test]
@@ -1077,4 +1416,23 @@ mod tests {
             panic!("Expected a CoreParameter.")
         }
     }
+
+    /// Test that `is_variable` is kept when a parameter is deserialized as a custom parameter.
+    #[test]
+    fn test_custom_parameter_is_variable() {
+        let data = r#"
+        {
+            "my-param": {"type": "mycustom", "is_variable": true, "value": 1}
+        }
+        "#;
+
+        let parameters: ParameterVec =
+            serde_json::from_str(data).expect("Failed to create Parameter from expected data!");
+
+        let Parameter::Custom(p) = &parameters[0] else {
+            panic!("Expected a custom parameter");
+        };
+        assert_eq!(p.meta.is_variable, Some(true));
+        assert!(!p.attributes.contains_key("is_variable"));
+    }
 }
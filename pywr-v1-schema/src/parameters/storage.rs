@@ -3,7 +3,8 @@ use pywr_v1_schema_macros::PywrParameter;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct StorageParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -16,4 +17,10 @@ impl StorageParameter {
             .into_iter()
             .collect()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        vec![("storage_node", &mut self.storage_node)]
+            .into_iter()
+            .collect()
+    }
 }
@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 #[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum Predicate {
     #[serde(alias = "<", alias = "lt", alias = "LT")]
     LT,
@@ -21,7 +22,8 @@ fn default_predicate() -> Predicate {
     Predicate::LT
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ParameterThresholdParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -36,9 +38,14 @@ impl ParameterThresholdParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct NodeThresholdParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -51,11 +58,16 @@ pub struct NodeThresholdParameter {
 
 impl NodeThresholdParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
-        HashMap::new()
+        vec![("node", self.node.as_str())].into_iter().collect()
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        vec![("node", &mut self.node)].into_iter().collect()
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct StorageThresholdParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -68,11 +80,20 @@ pub struct StorageThresholdParameter {
 
 impl StorageThresholdParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
-        HashMap::new()
+        vec![("storage_node", self.storage_node.as_str())]
+            .into_iter()
+            .collect()
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        vec![("storage_node", &mut self.storage_node)]
+            .into_iter()
+            .collect()
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct MultipleThresholdIndexParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -82,11 +103,16 @@ pub struct MultipleThresholdIndexParameter {
 
 impl MultipleThresholdIndexParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
-        HashMap::new()
+        vec![("node", self.node.as_str())].into_iter().collect()
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        vec![("node", &mut self.node)].into_iter().collect()
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct MultipleThresholdParameterIndexParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -98,9 +124,14 @@ impl MultipleThresholdParameterIndexParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CurrentYearThresholdParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -114,9 +145,14 @@ impl CurrentYearThresholdParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        HashMap::new()
+    }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PywrParameter)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrParameter)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CurrentOrdinalDayThresholdParameter {
     #[serde(flatten)]
     pub meta: Option<ParameterMeta>,
@@ -130,6 +166,10 @@ impl CurrentOrdinalDayThresholdParameter {
     pub fn node_references(&self) -> HashMap<&str, &str> {
         HashMap::new()
     }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, &mut String> {
+        HashMap::new()
+    }
 }
 
 #[cfg(test)]
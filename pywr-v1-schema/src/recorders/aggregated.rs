@@ -0,0 +1,24 @@
+use crate::parameters::{ParameterValueType, ParameterValueTypeMut};
+use crate::recorders::{RecorderAggFunc, RecorderMeta};
+use pywr_v1_schema_macros::PywrRecorder;
+use std::collections::HashMap;
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrRecorder)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct AggregatedRecorder {
+    #[serde(flatten)]
+    pub meta: RecorderMeta,
+    pub recorders: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recorder_agg_func: Option<RecorderAggFunc>,
+}
+
+impl AggregatedRecorder {
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        HashMap::new()
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        HashMap::new()
+    }
+}
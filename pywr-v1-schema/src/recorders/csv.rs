@@ -0,0 +1,43 @@
+use crate::parameters::{ParameterValueType, ParameterValueTypeMut};
+use crate::recorders::RecorderMeta;
+use pywr_v1_schema_macros::PywrRecorder;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrRecorder)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct CsvRecorder {
+    #[serde(flatten)]
+    pub meta: RecorderMeta,
+    pub url: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nodes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scenario_index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub complib: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub complevel: Option<u8>,
+    #[serde(flatten)]
+    pub csv_kwargs: HashMap<String, serde_json::Value>,
+}
+
+impl CsvRecorder {
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        match &self.nodes {
+            Some(nodes) => vec![("nodes", nodes.iter().map(|n| n.as_str()).collect())]
+                .into_iter()
+                .collect(),
+            None => HashMap::new(),
+        }
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        match &mut self.nodes {
+            Some(nodes) => vec![("nodes", nodes.iter_mut().collect())]
+                .into_iter()
+                .collect(),
+            None => HashMap::new(),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use crate::parameters::{ExternalDataRef, ParameterValueType, ParameterValueTypeMut, TableDataRef};
+use crate::recorders::{RecorderAggFunc, RecorderMeta};
+use pywr_v1_schema_macros::PywrRecorder;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// A target flow duration curve.
+///
+/// Targets can be given as a single curve, one curve per scenario, or loaded from
+/// an external file or table.
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum FdcTarget {
+    Values(Vec<f64>),
+    ScenarioValues(Vec<Vec<f64>>),
+    External(ExternalDataRef),
+    Table(TableDataRef),
+}
+
+impl FdcTarget {
+    fn resource_paths(&self) -> Vec<PathBuf> {
+        match self {
+            Self::External(external) => vec![external.url.clone()],
+            _ => Vec::new(),
+        }
+    }
+
+    fn update_resource_paths(&mut self, new_paths: &HashMap<PathBuf, PathBuf>) {
+        if let Self::External(external) = self
+            && let Some(new_path) = new_paths.get(&external.url)
+        {
+            external.url.clone_from(new_path);
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrRecorder)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct FlowDurationCurveDeviationRecorder {
+    #[serde(flatten)]
+    pub meta: RecorderMeta,
+    pub node: String,
+    pub percentiles: Vec<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lower_target_fdc: Option<FdcTarget>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upper_target_fdc: Option<FdcTarget>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scenario: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temporal_agg_func: Option<RecorderAggFunc>,
+}
+
+impl FlowDurationCurveDeviationRecorder {
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        vec![("node", vec![self.node.as_str()])]
+            .into_iter()
+            .collect()
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("node", vec![&mut self.node])].into_iter().collect()
+    }
+
+    pub fn resource_paths(&self) -> Vec<PathBuf> {
+        self.lower_target_fdc
+            .iter()
+            .chain(self.upper_target_fdc.iter())
+            .flat_map(|target| target.resource_paths())
+            .collect()
+    }
+
+    pub fn update_resource_paths(&mut self, new_paths: &HashMap<PathBuf, PathBuf>) {
+        if let Some(target) = &mut self.lower_target_fdc {
+            target.update_resource_paths(new_paths);
+        }
+        if let Some(target) = &mut self.upper_target_fdc {
+            target.update_resource_paths(new_paths);
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrRecorder)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct SeasonalFlowDurationCurveRecorder {
+    #[serde(flatten)]
+    pub meta: RecorderMeta,
+    pub node: String,
+    pub percentiles: Vec<f64>,
+    pub months: Vec<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temporal_agg_func: Option<RecorderAggFunc>,
+}
+
+impl SeasonalFlowDurationCurveRecorder {
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        vec![("node", vec![self.node.as_str()])]
+            .into_iter()
+            .collect()
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("node", vec![&mut self.node])].into_iter().collect()
+    }
+}
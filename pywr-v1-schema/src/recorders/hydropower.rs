@@ -0,0 +1,36 @@
+use crate::parameters::{ParameterValue, ParameterValueType, ParameterValueTypeMut};
+use crate::recorders::RecorderMeta;
+use pywr_v1_schema_macros::PywrRecorder;
+use std::collections::HashMap;
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrRecorder)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct HydropowerRecorder {
+    #[serde(flatten)]
+    pub meta: RecorderMeta,
+    pub node: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub water_elevation_parameter: Option<ParameterValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turbine_elevation: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub efficiency: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub density: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flow_unit_conversion: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub energy_unit_conversion: Option<f64>,
+}
+
+impl HydropowerRecorder {
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        vec![("node", vec![self.node.as_str()])]
+            .into_iter()
+            .collect()
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("node", vec![&mut self.node])].into_iter().collect()
+    }
+}
@@ -0,0 +1,684 @@
+mod aggregated;
+mod csv;
+mod fdc;
+mod hydropower;
+mod node;
+mod numpy;
+mod tables;
+
+use crate::parameters::{ParameterValueType, ParameterValueTypeMut};
+pub use crate::recorders::aggregated::AggregatedRecorder;
+pub use crate::recorders::csv::CsvRecorder;
+pub use crate::recorders::fdc::{
+    FdcTarget, FlowDurationCurveDeviationRecorder, SeasonalFlowDurationCurveRecorder,
+};
+pub use crate::recorders::hydropower::HydropowerRecorder;
+pub use crate::recorders::node::{
+    MeanFlowNodeRecorder, MinimumVolumeStorageRecorder, RollingMeanFlowNodeRecorder,
+    TotalDeficitNodeRecorder, TotalFlowNodeRecorder,
+};
+pub use crate::recorders::numpy::{
+    NumpyArrayNodeRecorder, NumpyArrayParameterRecorder, NumpyArrayStorageRecorder,
+};
+pub use crate::recorders::tables::{TablesRecorder, TablesRecorderEntry};
+use pywr_v1_schema_macros::PywrTaggedEnum;
+use serde::de::value::MapDeserializer;
+use serde::de::{MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+use std::vec::IntoIter;
+
+/// Whether a recorder is an objective to be maximised or minimised.
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ObjectiveDirection {
+    #[serde(alias = "maximize", alias = "max")]
+    Maximise,
+    #[serde(alias = "minimize", alias = "min")]
+    Minimise,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum AggFunc {
+    Sum,
+    Min,
+    Max,
+    Mean,
+    Median,
+    Product,
+    Percentile,
+    PercentileOfScore,
+    #[serde(rename = "count_nonzero")]
+    CountNonZero,
+    Any,
+    All,
+}
+
+/// An aggregation function with additional arguments (e.g. `percentile`).
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct CustomAggFunc {
+    pub func: AggFunc,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub args: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kwargs: Option<HashMap<String, Value>>,
+}
+
+/// A recorder aggregation function, given either by name or as a function with arguments.
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum RecorderAggFunc {
+    Simple(AggFunc),
+    Custom(CustomAggFunc),
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct RecorderMeta {
+    // Do not serialize name on the object as it is used as the key in the parent map,
+    // and we don't want to duplicate it in the output.
+    #[serde(skip_serializing)]
+    #[cfg_attr(feature = "json-schema", schemars(skip))]
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agg_func: Option<RecorderAggFunc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_nan: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_objective: Option<ObjectiveDirection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epsilon: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraint_lower_bounds: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraint_upper_bounds: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<HashMap<String, Value>>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct CustomRecorder {
+    #[serde(rename = "type")]
+    pub ty: String,
+    #[serde(flatten)]
+    pub meta: RecorderMeta,
+    #[serde(flatten)]
+    pub attributes: HashMap<String, Value>,
+}
+
+// As with parameters, aliases are used until serde supports case-insensitive
+// deserialization of tags.
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrTaggedEnum)]
+#[cfg_attr(
+    feature = "json-schema",
+    derive(schemars::JsonSchema),
+    schemars(transform = crate::json_schema::TypeAliases(CoreRecorder::TYPE_ALIASES))
+)]
+#[serde(tag = "type")]
+pub enum CoreRecorder {
+    #[serde(
+        alias = "aggregated",
+        alias = "aggregatedrecorder",
+        alias = "AggregatedRecorder"
+    )]
+    Aggregated(AggregatedRecorder),
+    #[serde(alias = "csv", alias = "csvrecorder", alias = "CSVRecorder")]
+    Csv(CsvRecorder),
+    #[serde(
+        alias = "flowdurationcurvedeviation",
+        alias = "flowdurationcurvedeviationrecorder",
+        alias = "FlowDurationCurveDeviationRecorder"
+    )]
+    FlowDurationCurveDeviation(FlowDurationCurveDeviationRecorder),
+    #[serde(
+        alias = "hydropower",
+        alias = "hydropowerrecorder",
+        alias = "HydropowerRecorder",
+        alias = "HydroPowerRecorder"
+    )]
+    Hydropower(HydropowerRecorder),
+    #[serde(
+        alias = "meanflownode",
+        alias = "meanflownoderecorder",
+        alias = "MeanFlowNodeRecorder"
+    )]
+    MeanFlowNode(MeanFlowNodeRecorder),
+    #[serde(
+        alias = "minimumvolumestorage",
+        alias = "minimumvolumestoragerecorder",
+        alias = "MinimumVolumeStorageRecorder"
+    )]
+    MinimumVolumeStorage(MinimumVolumeStorageRecorder),
+    #[serde(
+        alias = "numpyarraynode",
+        alias = "numpyarraynoderecorder",
+        alias = "NumpyArrayNodeRecorder"
+    )]
+    NumpyArrayNode(NumpyArrayNodeRecorder),
+    #[serde(
+        alias = "numpyarrayparameter",
+        alias = "numpyarrayparameterrecorder",
+        alias = "NumpyArrayParameterRecorder"
+    )]
+    NumpyArrayParameter(NumpyArrayParameterRecorder),
+    #[serde(
+        alias = "numpyarraystorage",
+        alias = "numpyarraystoragerecorder",
+        alias = "NumpyArrayStorageRecorder"
+    )]
+    NumpyArrayStorage(NumpyArrayStorageRecorder),
+    #[serde(
+        alias = "rollingmeanflownode",
+        alias = "rollingmeanflownoderecorder",
+        alias = "RollingMeanFlowNodeRecorder"
+    )]
+    RollingMeanFlowNode(RollingMeanFlowNodeRecorder),
+    #[serde(
+        alias = "seasonalflowdurationcurve",
+        alias = "seasonalflowdurationcurverecorder",
+        alias = "SeasonalFlowDurationCurveRecorder"
+    )]
+    SeasonalFlowDurationCurve(SeasonalFlowDurationCurveRecorder),
+    #[serde(alias = "tables", alias = "tablesrecorder", alias = "TablesRecorder")]
+    Tables(TablesRecorder),
+    #[serde(
+        alias = "totaldeficitnode",
+        alias = "totaldeficitnoderecorder",
+        alias = "TotalDeficitNodeRecorder"
+    )]
+    TotalDeficitNode(TotalDeficitNodeRecorder),
+    #[serde(
+        alias = "totalflownode",
+        alias = "totalflownoderecorder",
+        alias = "TotalFlowNodeRecorder"
+    )]
+    TotalFlowNode(TotalFlowNodeRecorder),
+}
+
+impl CoreRecorder {
+    pub fn name(&self) -> &str {
+        self.meta().name.as_str()
+    }
+
+    pub fn meta(&self) -> &RecorderMeta {
+        match self {
+            Self::Aggregated(r) => &r.meta,
+            Self::Csv(r) => &r.meta,
+            Self::FlowDurationCurveDeviation(r) => &r.meta,
+            Self::Hydropower(r) => &r.meta,
+            Self::MeanFlowNode(r) => &r.meta,
+            Self::MinimumVolumeStorage(r) => &r.meta,
+            Self::NumpyArrayNode(r) => &r.meta,
+            Self::NumpyArrayParameter(r) => &r.meta,
+            Self::NumpyArrayStorage(r) => &r.meta,
+            Self::RollingMeanFlowNode(r) => &r.meta,
+            Self::SeasonalFlowDurationCurve(r) => &r.meta,
+            Self::Tables(r) => &r.meta,
+            Self::TotalDeficitNode(r) => &r.meta,
+            Self::TotalFlowNode(r) => &r.meta,
+        }
+    }
+
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        match self {
+            Self::Aggregated(r) => r.node_references(),
+            Self::Csv(r) => r.node_references(),
+            Self::FlowDurationCurveDeviation(r) => r.node_references(),
+            Self::Hydropower(r) => r.node_references(),
+            Self::MeanFlowNode(r) => r.node_references(),
+            Self::MinimumVolumeStorage(r) => r.node_references(),
+            Self::NumpyArrayNode(r) => r.node_references(),
+            Self::NumpyArrayParameter(r) => r.node_references(),
+            Self::NumpyArrayStorage(r) => r.node_references(),
+            Self::RollingMeanFlowNode(r) => r.node_references(),
+            Self::SeasonalFlowDurationCurve(r) => r.node_references(),
+            Self::Tables(r) => r.node_references(),
+            Self::TotalDeficitNode(r) => r.node_references(),
+            Self::TotalFlowNode(r) => r.node_references(),
+        }
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        match self {
+            Self::Aggregated(r) => r.node_references_mut(),
+            Self::Csv(r) => r.node_references_mut(),
+            Self::FlowDurationCurveDeviation(r) => r.node_references_mut(),
+            Self::Hydropower(r) => r.node_references_mut(),
+            Self::MeanFlowNode(r) => r.node_references_mut(),
+            Self::MinimumVolumeStorage(r) => r.node_references_mut(),
+            Self::NumpyArrayNode(r) => r.node_references_mut(),
+            Self::NumpyArrayParameter(r) => r.node_references_mut(),
+            Self::NumpyArrayStorage(r) => r.node_references_mut(),
+            Self::RollingMeanFlowNode(r) => r.node_references_mut(),
+            Self::SeasonalFlowDurationCurve(r) => r.node_references_mut(),
+            Self::Tables(r) => r.node_references_mut(),
+            Self::TotalDeficitNode(r) => r.node_references_mut(),
+            Self::TotalFlowNode(r) => r.node_references_mut(),
+        }
+    }
+
+    pub fn parameters(&self) -> HashMap<&str, ParameterValueType<'_>> {
+        match self {
+            Self::Aggregated(r) => r.parameters(),
+            Self::Csv(r) => r.parameters(),
+            Self::FlowDurationCurveDeviation(r) => r.parameters(),
+            Self::Hydropower(r) => r.parameters(),
+            Self::MeanFlowNode(r) => r.parameters(),
+            Self::MinimumVolumeStorage(r) => r.parameters(),
+            Self::NumpyArrayNode(r) => r.parameters(),
+            Self::NumpyArrayParameter(r) => r.parameters(),
+            Self::NumpyArrayStorage(r) => r.parameters(),
+            Self::RollingMeanFlowNode(r) => r.parameters(),
+            Self::SeasonalFlowDurationCurve(r) => r.parameters(),
+            Self::Tables(r) => r.parameters(),
+            Self::TotalDeficitNode(r) => r.parameters(),
+            Self::TotalFlowNode(r) => r.parameters(),
+        }
+    }
+
+    pub fn parameters_mut(&mut self) -> HashMap<&str, ParameterValueTypeMut<'_>> {
+        match self {
+            Self::Aggregated(r) => r.parameters_mut(),
+            Self::Csv(r) => r.parameters_mut(),
+            Self::FlowDurationCurveDeviation(r) => r.parameters_mut(),
+            Self::Hydropower(r) => r.parameters_mut(),
+            Self::MeanFlowNode(r) => r.parameters_mut(),
+            Self::MinimumVolumeStorage(r) => r.parameters_mut(),
+            Self::NumpyArrayNode(r) => r.parameters_mut(),
+            Self::NumpyArrayParameter(r) => r.parameters_mut(),
+            Self::NumpyArrayStorage(r) => r.parameters_mut(),
+            Self::RollingMeanFlowNode(r) => r.parameters_mut(),
+            Self::SeasonalFlowDurationCurve(r) => r.parameters_mut(),
+            Self::Tables(r) => r.parameters_mut(),
+            Self::TotalDeficitNode(r) => r.parameters_mut(),
+            Self::TotalFlowNode(r) => r.parameters_mut(),
+        }
+    }
+
+    pub fn ty(&self) -> &'static str {
+        match self {
+            Self::Aggregated(_) => "Aggregated",
+            Self::Csv(_) => "Csv",
+            Self::FlowDurationCurveDeviation(_) => "FlowDurationCurveDeviation",
+            Self::Hydropower(_) => "Hydropower",
+            Self::MeanFlowNode(_) => "MeanFlowNode",
+            Self::MinimumVolumeStorage(_) => "MinimumVolumeStorage",
+            Self::NumpyArrayNode(_) => "NumpyArrayNode",
+            Self::NumpyArrayParameter(_) => "NumpyArrayParameter",
+            Self::NumpyArrayStorage(_) => "NumpyArrayStorage",
+            Self::RollingMeanFlowNode(_) => "RollingMeanFlowNode",
+            Self::SeasonalFlowDurationCurve(_) => "SeasonalFlowDurationCurve",
+            Self::Tables(_) => "Tables",
+            Self::TotalDeficitNode(_) => "TotalDeficitNode",
+            Self::TotalFlowNode(_) => "TotalFlowNode",
+        }
+    }
+
+    /// Return any external resource paths referenced by this recorder.
+    ///
+    /// Output files written by a recorder (e.g. [`CsvRecorder`]) are not included.
+    pub fn resource_paths(&self) -> Vec<PathBuf> {
+        let mut resource_paths = match self {
+            Self::FlowDurationCurveDeviation(r) => r.resource_paths(),
+            _ => Vec::new(),
+        };
+
+        for (_, value_type) in self.parameters() {
+            match value_type {
+                ParameterValueType::Single(value) => resource_paths.extend(value.resource_paths()),
+                ParameterValueType::List(values) => {
+                    for value in values {
+                        resource_paths.extend(value.resource_paths());
+                    }
+                }
+                ParameterValueType::OptionalList(values) => {
+                    for value in values.iter().flatten() {
+                        resource_paths.extend(value.resource_paths());
+                    }
+                }
+            }
+        }
+
+        resource_paths
+    }
+
+    pub fn update_resource_paths(&mut self, new_paths: &HashMap<PathBuf, PathBuf>) {
+        if let Self::FlowDurationCurveDeviation(r) = self {
+            r.update_resource_paths(new_paths);
+        }
+
+        for (_, value_type) in self.parameters_mut() {
+            match value_type {
+                ParameterValueTypeMut::Single(value) => value.update_resource_paths(new_paths),
+                ParameterValueTypeMut::List(values) => {
+                    for value in values {
+                        value.update_resource_paths(new_paths);
+                    }
+                }
+                ParameterValueTypeMut::OptionalList(values) => {
+                    for value in values.iter_mut().flatten() {
+                        value.update_resource_paths(new_paths);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Boxing the core variant leaves the custom variant as the large one, so the size
+// difference is accepted here.
+#[allow(clippy::large_enum_variant)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum Recorder {
+    Core(CoreRecorder),
+    Custom(CustomRecorder),
+}
+
+impl Recorder {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Core(r) => r.name(),
+            Self::Custom(r) => r.meta.name.as_str(),
+        }
+    }
+
+    pub fn meta(&self) -> &RecorderMeta {
+        match self {
+            Self::Core(r) => r.meta(),
+            Self::Custom(r) => &r.meta,
+        }
+    }
+
+    /// Return a map of attribute to node references.
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        match self {
+            Self::Core(r) => r.node_references(),
+            Self::Custom(_) => HashMap::new(),
+        }
+    }
+
+    /// Return a map of attribute to mutable node references.
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        match self {
+            Self::Core(r) => r.node_references_mut(),
+            Self::Custom(_) => HashMap::new(),
+        }
+    }
+
+    /// Return a map of attribute to parameter values.
+    pub fn parameters(&self) -> HashMap<&str, ParameterValueType<'_>> {
+        match self {
+            Self::Core(r) => r.parameters(),
+            Self::Custom(_) => HashMap::new(),
+        }
+    }
+
+    pub fn parameters_mut(&mut self) -> HashMap<&str, ParameterValueTypeMut<'_>> {
+        match self {
+            Self::Core(r) => r.parameters_mut(),
+            Self::Custom(_) => HashMap::new(),
+        }
+    }
+
+    /// Return the type of the recorder
+    pub fn ty(&self) -> &str {
+        match self {
+            Self::Core(r) => r.ty(),
+            Self::Custom(r) => r.ty.as_str(),
+        }
+    }
+
+    /// Return any external resource paths referenced by this recorder
+    pub fn resource_paths(&self) -> Vec<PathBuf> {
+        match self {
+            Self::Core(r) => r.resource_paths(),
+            // It is not possible to determine external resources for custom recorders
+            Self::Custom(_) => Vec::new(),
+        }
+    }
+
+    /// Update any external resource paths referenced by this recorder if they are
+    /// in the provided map.
+    pub fn update_resource_paths(&mut self, new_paths: &HashMap<PathBuf, PathBuf>) {
+        match self {
+            Self::Core(r) => r.update_resource_paths(new_paths),
+            Self::Custom(_) => {}
+        }
+    }
+
+    /// Return true if this is a custom recorder.
+    pub fn is_custom(&self) -> bool {
+        match self {
+            Self::Core(_) => false,
+            Self::Custom(_) => true,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Default)]
+pub struct RecorderVec(Vec<Recorder>);
+
+impl RecorderVec {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+}
+
+impl IntoIterator for RecorderVec {
+    type Item = Recorder;
+    type IntoIter = IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl Deref for RecorderVec {
+    type Target = Vec<Recorder>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for RecorderVec {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+struct PywrRecorderMapVisitor {
+    marker: PhantomData<fn() -> RecorderVec>,
+}
+
+impl PywrRecorderMapVisitor {
+    fn new() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+fn remove_suffix<'a>(s: &'a str, suffix: &str) -> &'a str {
+    match s.strip_suffix(suffix) {
+        Some(s) => s,
+        None => s,
+    }
+}
+
+impl<'de> Visitor<'de> for PywrRecorderMapVisitor {
+    type Value = RecorderVec;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a valid Pywr recorder definition")
+    }
+
+    fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut map = RecorderVec::with_capacity(access.size_hint().unwrap_or(0));
+
+        #[derive(serde::Deserialize, Debug)]
+        struct Helper {
+            pub tags: Option<HashMap<String, Value>>,
+            pub comment: Option<String>,
+            #[serde(rename = "type")]
+            pub ty: String,
+            #[serde(flatten)]
+            attributes: HashMap<String, Value>,
+        }
+
+        // While there are entries remaining in the input, add them into our map.
+        while let Some((name, value)) = access.next_entry::<String, Helper>()? {
+            let ty = value.ty.to_lowercase();
+            let ty = remove_suffix(&ty, "recorder");
+
+            // Try to deserialize the recorder as a core Pywr recorder.
+            // If that fails assume it is a custom recorder
+            // First we need to create a copy of the attributes containing both the name and type
+            // keys
+            let mut py_attributes = value.attributes.clone();
+            py_attributes.insert("name".to_string(), Value::String(name.clone()));
+            py_attributes.insert("type".to_string(), Value::String(ty.to_string()));
+            if let Some(comment) = &value.comment {
+                py_attributes.insert("comment".to_string(), Value::String(comment.clone()));
+            }
+            if let Some(tags) = &value.tags {
+                let value = tags.clone().into_iter().collect();
+                py_attributes.insert("tags".to_string(), Value::Object(value));
+            }
+
+            let r = match CoreRecorder::deserialize(MapDeserializer::new(py_attributes.into_iter()))
+            {
+                Ok(r) => Recorder::Core(r),
+                // Deserializing a core recorder failed; deserialize as a custom recorder
+                Err(_) => Recorder::Custom(CustomRecorder {
+                    meta: RecorderMeta {
+                        name,
+                        comment: value.comment,
+                        agg_func: None,
+                        ignore_nan: None,
+                        is_objective: None,
+                        epsilon: None,
+                        constraint_lower_bounds: None,
+                        constraint_upper_bounds: None,
+                        tags: value.tags,
+                    },
+                    ty: value.ty,
+                    attributes: value.attributes,
+                }),
+            };
+
+            map.push(r);
+        }
+
+        Ok(map)
+    }
+}
+
+impl<'de> Deserialize<'de> for RecorderVec {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(PywrRecorderMapVisitor::new())
+    }
+}
+
+impl Serialize for RecorderVec {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+
+        for r in &self.0 {
+            map.serialize_entry(r.name(), r)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::recorders::{CoreRecorder, ObjectiveDirection, Recorder, RecorderVec};
+
+    #[test]
+    fn test_recorder_map() {
+        let data = r#"
+        {
+            "deficit1": {
+                "type": "totaldeficitnode",
+                "node": "demand1"
+            },
+            "deficit": {
+                "type": "aggregated",
+                "recorders": ["deficit1"],
+                "recorder_agg_func": "sum",
+                "is_objective": "minimise"
+            },
+            "supply": {
+                "comment": "Actual flow from supply1",
+                "type": "NumpyArrayNodeRecorder",
+                "node": "supply1"
+            },
+            "custom": {
+                "type": "MyCustomRecorder",
+                "node": "supply1",
+                "comment": "A custom recorder"
+            }
+        }
+        "#;
+
+        let recorders: RecorderVec = serde_json::from_str(data).unwrap();
+
+        assert_eq!(recorders.len(), 4);
+        assert_eq!(recorders[0].ty(), "TotalDeficitNode");
+        assert_eq!(
+            recorders[0].node_references().remove("node"),
+            Some(vec!["demand1"])
+        );
+
+        match &recorders[1] {
+            Recorder::Core(r) => match r {
+                CoreRecorder::Aggregated(r) => {
+                    assert_eq!(r.meta.name, "deficit");
+                    assert_eq!(r.recorders, vec!["deficit1".to_string()]);
+                    assert_eq!(r.meta.is_objective, Some(ObjectiveDirection::Minimise));
+                }
+                _ => panic!("Incorrect core recorder deserialized."),
+            },
+            _ => panic!("Non-core recorder was deserialized."),
+        }
+
+        assert_eq!(
+            recorders[2].meta().comment.as_deref(),
+            Some("Actual flow from supply1")
+        );
+
+        assert!(recorders[3].is_custom());
+        assert_eq!(recorders[3].name(), "custom");
+        assert_eq!(recorders[3].ty(), "MyCustomRecorder");
+        assert_eq!(
+            recorders[3].meta().comment.as_deref(),
+            Some("A custom recorder")
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use crate::parameters::{ParameterValueType, ParameterValueTypeMut};
+use crate::recorders::RecorderMeta;
+use pywr_v1_schema_macros::PywrRecorder;
+use std::collections::HashMap;
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrRecorder)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct TotalDeficitNodeRecorder {
+    #[serde(flatten)]
+    pub meta: RecorderMeta,
+    pub node: String,
+}
+
+impl TotalDeficitNodeRecorder {
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        vec![("node", vec![self.node.as_str()])]
+            .into_iter()
+            .collect()
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("node", vec![&mut self.node])].into_iter().collect()
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrRecorder)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct TotalFlowNodeRecorder {
+    #[serde(flatten)]
+    pub meta: RecorderMeta,
+    pub node: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub factor: Option<f64>,
+}
+
+impl TotalFlowNodeRecorder {
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        vec![("node", vec![self.node.as_str()])]
+            .into_iter()
+            .collect()
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("node", vec![&mut self.node])].into_iter().collect()
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrRecorder)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct MeanFlowNodeRecorder {
+    #[serde(flatten)]
+    pub meta: RecorderMeta,
+    pub node: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub factor: Option<f64>,
+}
+
+impl MeanFlowNodeRecorder {
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        vec![("node", vec![self.node.as_str()])]
+            .into_iter()
+            .collect()
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("node", vec![&mut self.node])].into_iter().collect()
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrRecorder)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct RollingMeanFlowNodeRecorder {
+    #[serde(flatten)]
+    pub meta: RecorderMeta,
+    pub node: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timesteps: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days: Option<i64>,
+}
+
+impl RollingMeanFlowNodeRecorder {
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        vec![("node", vec![self.node.as_str()])]
+            .into_iter()
+            .collect()
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("node", vec![&mut self.node])].into_iter().collect()
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrRecorder)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct MinimumVolumeStorageRecorder {
+    #[serde(flatten)]
+    pub meta: RecorderMeta,
+    pub node: String,
+}
+
+impl MinimumVolumeStorageRecorder {
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        vec![("node", vec![self.node.as_str()])]
+            .into_iter()
+            .collect()
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("node", vec![&mut self.node])].into_iter().collect()
+    }
+}
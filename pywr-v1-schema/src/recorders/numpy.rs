@@ -0,0 +1,72 @@
+use crate::parameters::{ParameterValue, ParameterValueType, ParameterValueTypeMut};
+use crate::recorders::{RecorderAggFunc, RecorderMeta};
+use pywr_v1_schema_macros::PywrRecorder;
+use std::collections::HashMap;
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrRecorder)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct NumpyArrayNodeRecorder {
+    #[serde(flatten)]
+    pub meta: RecorderMeta,
+    pub node: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temporal_agg_func: Option<RecorderAggFunc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub factor: Option<f64>,
+}
+
+impl NumpyArrayNodeRecorder {
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        vec![("node", vec![self.node.as_str()])]
+            .into_iter()
+            .collect()
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("node", vec![&mut self.node])].into_iter().collect()
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrRecorder)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct NumpyArrayStorageRecorder {
+    #[serde(flatten)]
+    pub meta: RecorderMeta,
+    pub node: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temporal_agg_func: Option<RecorderAggFunc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proportional: Option<bool>,
+}
+
+impl NumpyArrayStorageRecorder {
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        vec![("node", vec![self.node.as_str()])]
+            .into_iter()
+            .collect()
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        vec![("node", vec![&mut self.node])].into_iter().collect()
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrRecorder)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct NumpyArrayParameterRecorder {
+    #[serde(flatten)]
+    pub meta: RecorderMeta,
+    pub parameter: ParameterValue,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temporal_agg_func: Option<RecorderAggFunc>,
+}
+
+impl NumpyArrayParameterRecorder {
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        HashMap::new()
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        HashMap::new()
+    }
+}
@@ -0,0 +1,84 @@
+use crate::parameters::{ParameterValueType, ParameterValueTypeMut};
+use crate::recorders::RecorderMeta;
+use pywr_v1_schema_macros::PywrRecorder;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// An entry in a [`TablesRecorder`]'s nodes or parameters list.
+///
+/// Entries are either the name of the element or a pair of the HDF5 location
+/// and the element's name.
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum TablesRecorderEntry {
+    Name(String),
+    WithWhere(String, String),
+}
+
+impl TablesRecorderEntry {
+    /// Return the name of the node or parameter to be recorded.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Name(name) => name.as_str(),
+            Self::WithWhere(_, name) => name.as_str(),
+        }
+    }
+
+    /// Return a mutable reference to the name of the node or parameter to be recorded.
+    pub fn name_mut(&mut self) -> &mut String {
+        match self {
+            Self::Name(name) => name,
+            Self::WithWhere(_, name) => name,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, PywrRecorder)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct TablesRecorder {
+    #[serde(flatten)]
+    pub meta: RecorderMeta,
+    pub url: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nodes: Option<Vec<TablesRecorderEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<Vec<TablesRecorderEntry>>,
+    #[serde(rename = "where", skip_serializing_if = "Option::is_none")]
+    pub wh: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routes_flows: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scenarios: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_kwds: Option<HashMap<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub create_directories: Option<bool>,
+}
+
+impl TablesRecorder {
+    pub fn node_references(&self) -> HashMap<&str, Vec<&str>> {
+        match &self.nodes {
+            Some(nodes) => vec![("nodes", nodes.iter().map(|n| n.name()).collect())]
+                .into_iter()
+                .collect(),
+            None => HashMap::new(),
+        }
+    }
+
+    pub fn node_references_mut(&mut self) -> HashMap<&str, Vec<&mut String>> {
+        match &mut self.nodes {
+            Some(nodes) => vec![("nodes", nodes.iter_mut().map(|n| n.name_mut()).collect())]
+                .into_iter()
+                .collect(),
+            None => HashMap::new(),
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use crate::PywrSchemaError;
+use crate::json_path::PathSegment;
+use crate::location::{deserialize_value_at, error_at, parse_json};
+use crate::model::{PywrModel, PywrNetwork};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::fs;
+use std::ops::DerefMut;
+use std::path::Path;
+
+impl PywrNetwork {
+    /// Load a PywrNetwork from a file path, collecting every error rather than stopping at
+    /// the first.
+    ///
+    /// Each node, edge, parameter, table and recorder is deserialized independently and any
+    /// that fail are omitted from the network. The network is only `None` if the file cannot
+    /// be read or is not a JSON object. The errors are sorted by their position in the file.
+    pub fn from_path_recovering<P: AsRef<Path>>(path: P) -> (Option<Self>, Vec<PywrSchemaError>) {
+        match fs::read_to_string(path) {
+            Ok(source) => recover(&source, |loader, mut object| {
+                Some(loader.network(&mut object))
+            }),
+            Err(error) => (None, vec![error.into()]),
+        }
+    }
+}
+
+impl PywrModel {
+    /// Load a PywrModel from a file path, collecting every error rather than stopping at the
+    /// first.
+    ///
+    /// The network is loaded as described in [`PywrNetwork::from_path_recovering`], and
+    /// scenarios and scenario combinations are also deserialized independently. The model is
+    /// `None` if its metadata or timestepper cannot be deserialized, but any errors in the
+    /// rest of the model are still returned. As with [`PywrModel::from_path`], only a
+    /// [`PywrSchemaError::MultiModelFile`] error is returned for a multi-model file.
+    pub fn from_path_recovering<P: AsRef<Path>>(path: P) -> (Option<Self>, Vec<PywrSchemaError>) {
+        match fs::read_to_string(path) {
+            Ok(source) => recover_model(&source),
+            Err(error) => (None, vec![error.into()]),
+        }
+    }
+}
+
+fn recover_model(source: &str) -> (Option<PywrModel>, Vec<PywrSchemaError>) {
+    recover(source, |loader, mut object| {
+        if object.contains_key("models") {
+            loader.errors.push(PywrSchemaError::MultiModelFile);
+            return None;
+        }
+
+        let metadata = loader.required(&mut object, "metadata");
+        let timestepper = loader.required(&mut object, "timestepper");
+        let scenarios = loader.list(&mut object, "scenarios");
+        let scenario_combinations = loader.list(&mut object, "scenario_combinations");
+        let network = loader.network(&mut object);
+
+        Some(PywrModel {
+            metadata: metadata?,
+            timestepper: timestepper?,
+            scenarios,
+            scenario_combinations,
+            network,
+        })
+    })
+}
+
+/// Parse `source` and build a `T` from its top-level object using `build`, returning every
+/// error found.
+fn recover<T, F>(source: &str, build: F) -> (Option<T>, Vec<PywrSchemaError>)
+where
+    T: DeserializeOwned,
+    F: FnOnce(&mut Loader, Map<String, Value>) -> Option<T>,
+{
+    let value = match parse_json(source) {
+        Ok(value) => value,
+        Err(error) => return (None, vec![error]),
+    };
+
+    let mut loader = Loader {
+        source,
+        errors: Vec::new(),
+    };
+
+    let result = match value {
+        Value::Object(object) => build(&mut loader, object),
+        // Deserialize anything else as a whole to report why it is invalid.
+        value => loader.deserialize(value, &[]),
+    };
+
+    let mut errors = loader.errors;
+    errors.sort_by_key(|error| match error {
+        PywrSchemaError::DeserializationError { line, column, .. } => (*line, *column),
+        _ => (0, 0),
+    });
+
+    (result, errors)
+}
+
+/// Deserializes the parts of a JSON document independently, recording any errors.
+struct Loader<'a> {
+    source: &'a str,
+    errors: Vec<PywrSchemaError>,
+}
+
+impl Loader<'_> {
+    /// Deserialize `value`, which is found at `path`, recording any error.
+    fn deserialize<T: DeserializeOwned>(
+        &mut self,
+        value: Value,
+        path: &[PathSegment],
+    ) -> Option<T> {
+        match deserialize_value_at(self.source, value, path) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.errors.push(error);
+                None
+            }
+        }
+    }
+
+    fn network(&mut self, object: &mut Map<String, Value>) -> PywrNetwork {
+        PywrNetwork {
+            includes: self.optional(object, "includes"),
+            nodes: self.list(object, "nodes"),
+            edges: self.list(object, "edges"),
+            parameters: self.named_map(object, "parameters"),
+            tables: self.named_map(object, "tables"),
+            recorders: self.named_map(object, "recorders"),
+        }
+    }
+
+    /// Deserialize the attribute `key` of the top-level `object`, recording an error if it
+    /// is missing.
+    fn required<T: DeserializeOwned>(
+        &mut self,
+        object: &mut Map<String, Value>,
+        key: &str,
+    ) -> Option<T> {
+        match object.remove(key) {
+            Some(value) => self.deserialize(value, &[PathSegment::Key(key.to_string())]),
+            None => {
+                let message = format!("missing field `{key}`");
+                self.errors.push(error_at(self.source, &[], message));
+                None
+            }
+        }
+    }
+
+    /// Deserialize the optional attribute `key` of the top-level `object`.
+    fn optional<T: DeserializeOwned>(
+        &mut self,
+        object: &mut Map<String, Value>,
+        key: &str,
+    ) -> Option<T> {
+        let value = object.remove(key)?;
+        self.deserialize::<Option<T>>(value, &[PathSegment::Key(key.to_string())])
+            .flatten()
+    }
+
+    /// Deserialize each element of the optional list `key` of the top-level `object`,
+    /// omitting any that fail.
+    fn list<T: DeserializeOwned>(
+        &mut self,
+        object: &mut Map<String, Value>,
+        key: &str,
+    ) -> Option<Vec<T>> {
+        match object.remove(key)? {
+            Value::Array(values) => Some(
+                values
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(index, value)| {
+                        self.deserialize(
+                            value,
+                            &[PathSegment::Key(key.to_string()), PathSegment::Index(index)],
+                        )
+                    })
+                    .collect(),
+            ),
+            value => self
+                .deserialize::<Option<Vec<T>>>(value, &[PathSegment::Key(key.to_string())])
+                .flatten(),
+        }
+    }
+
+    /// Deserialize each entry of the optional map of components `key` of the top-level
+    /// `object`, omitting any that fail.
+    ///
+    /// Each entry is deserialized as a map of its own, so that it is loaded in the same way
+    /// as when the whole map is deserialized.
+    fn named_map<V, T>(&mut self, object: &mut Map<String, Value>, key: &str) -> Option<V>
+    where
+        V: DeserializeOwned + Default + DerefMut<Target = Vec<T>>,
+    {
+        match object.remove(key)? {
+            Value::Object(entries) => {
+                let mut components = V::default();
+                for (name, value) in entries {
+                    let entry = Value::Object(Map::from_iter([(name.clone(), value)]));
+                    match deserialize_value_at::<V>(
+                        self.source,
+                        entry,
+                        &[PathSegment::Key(key.to_string())],
+                    ) {
+                        Ok(mut component) => components.append(&mut component),
+                        // Some components are deserialized after their entry has been read,
+                        // which loses the path to the entry; point the error at it instead.
+                        Err(PywrSchemaError::DeserializationError { message, path, .. })
+                            if path == key =>
+                        {
+                            let path = [PathSegment::Key(key.to_string()), PathSegment::Key(name)];
+                            self.errors.push(error_at(self.source, &path, message));
+                        }
+                        Err(error) => self.errors.push(error),
+                    }
+                }
+                Some(components)
+            }
+            value => self
+                .deserialize::<Option<V>>(value, &[PathSegment::Key(key.to_string())])
+                .flatten(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::recover_model;
+    use crate::PywrModel;
+    use crate::PywrSchemaError;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn test_model_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("models")
+    }
+
+    fn error_paths(errors: &[PywrSchemaError]) -> Vec<&str> {
+        errors
+            .iter()
+            .map(|e| match e {
+                PywrSchemaError::DeserializationError { path, .. } => path.as_str(),
+                e => panic!("Unexpected error: {e:?}"),
+            })
+            .collect()
+    }
+
+    /// Valid models should load without errors, and be the same as when loaded normally.
+    #[test]
+    fn test_pywr_models_recover_without_errors() {
+        for entry in fs::read_dir(test_model_dir()).unwrap() {
+            let path = entry.unwrap().path();
+            // `extra2.json` is a network included by `extra1.json` rather than a model.
+            if path.extension().is_none_or(|ext| ext != "json") || path.ends_with("extra2.json") {
+                continue;
+            }
+
+            let (model, errors) = PywrModel::from_path_recovering(&path);
+            assert!(errors.is_empty(), "{path:?} has errors: {errors:?}");
+            assert!(model == Some(PywrModel::from_path(&path).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_all_errors_are_collected() {
+        let data = r#"{
+    "metadata": {"title": "Errors", "minimum_version": "0.1"},
+    "timestepper": {"start": "2015-01-01", "end": "2015-12-31", "timestep": 1},
+    "scenarios": [{"name": "demand", "size": 10}, {"name": "inflow"}],
+    "nodes": [
+        {"name": "supply1", "type": "Input"},
+        {"type": "Link"},
+        {"name": "demand1", "type": "Output"},
+        {"name": "demand2"}
+    ],
+    "edges": [["supply1", "demand1"], ["supply1"]],
+    "parameters": {
+        "p1": {"type": "constant", "value": 1.0},
+        "p2": {"value": 2.0}
+    },
+    "tables": {
+        "t1": {"url": "data.csv"},
+        "t2": {"index": "date"}
+    }
+}"#;
+
+        let (model, errors) = recover_model(data);
+
+        assert_eq!(
+            error_paths(&errors),
+            vec![
+                "scenarios[1]",
+                "nodes[1]",
+                "nodes[3]",
+                "edges[1]",
+                "parameters.p2",
+                "tables.t2"
+            ]
+        );
+
+        let network = model.unwrap().network;
+        let nodes: Vec<_> = network
+            .nodes
+            .unwrap()
+            .iter()
+            .map(|n| n.name().to_string())
+            .collect();
+        assert_eq!(nodes, vec!["supply1", "demand1"]);
+        assert_eq!(network.edges.unwrap().len(), 1);
+        assert_eq!(network.parameters.unwrap().len(), 1);
+        assert_eq!(network.tables.unwrap().len(), 1);
+    }
+
+    /// Errors in the network are still reported if the model itself cannot be loaded.
+    #[test]
+    fn test_missing_timestepper() {
+        let data = r#"{
+    "metadata": {"title": "Errors", "minimum_version": "0.1"},
+    "nodes": [{"type": "Input"}]
+}"#;
+
+        let (model, errors) = recover_model(data);
+
+        assert!(model.is_none());
+        assert_eq!(error_paths(&errors), vec!["", "nodes[0]"]);
+        assert_eq!(
+            errors[0].to_string(),
+            "missing field `timestepper` (line 1, column 1)"
+        );
+    }
+}
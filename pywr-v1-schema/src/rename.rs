@@ -0,0 +1,447 @@
+use crate::PywrSchemaError;
+use crate::model::PywrNetwork;
+use crate::nodes::Node;
+use crate::parameters::{ParameterValue, ParameterValueTypeMut, visit_parameter_values_mut};
+use crate::recorders::{CoreRecorder, Recorder};
+use crate::validation::ValidationLocation;
+use std::collections::{HashMap, HashSet};
+
+impl PywrNetwork {
+    /// Rename the node `old` to `new`, updating all references to it.
+    ///
+    /// References are updated in the network's edges, in other nodes (e.g. the members of an
+    /// aggregated node), and in parameters and recorders, including inline parameters. If the
+    /// node has sub-nodes in Pywr, references to them (e.g. `link1 Gross`) are renamed too.
+    /// References from custom components are not known, and so are not updated.
+    ///
+    /// Returns the locations that were changed, starting with the renamed node itself. An
+    /// error is returned if there is no node named `old`, or a node named `new` already exists.
+    pub fn rename_node(
+        &mut self,
+        old: &str,
+        new: &str,
+    ) -> Result<Vec<ValidationLocation>, PywrSchemaError> {
+        let names: HashSet<String> = self
+            .nodes
+            .iter()
+            .flatten()
+            .map(|n| n.name().to_string())
+            .collect();
+
+        if names.contains(new) {
+            return Err(PywrSchemaError::NameAlreadyExists {
+                component: "node",
+                name: new.to_string(),
+            });
+        }
+
+        let node = self
+            .nodes
+            .iter_mut()
+            .flatten()
+            .find(|n| n.name() == old)
+            .ok_or_else(|| PywrSchemaError::ComponentNotFound {
+                component: "node",
+                name: old.to_string(),
+            })?;
+
+        let renamer = NodeRenamer {
+            old,
+            new,
+            node: node.clone(),
+            names,
+        };
+
+        node.meta_mut().name = new.to_string();
+        let mut changes = vec![ValidationLocation::Node {
+            name: new.to_string(),
+            attribute: None,
+        }];
+
+        for (index, edge) in self.edges.iter_mut().flatten().enumerate() {
+            let from_node = renamer.rename(&mut edge.from_node);
+            let to_node = renamer.rename(&mut edge.to_node);
+            if from_node || to_node {
+                changes.push(ValidationLocation::Edge {
+                    index,
+                    from_node: edge.from_node.clone(),
+                    to_node: edge.to_node.clone(),
+                });
+            }
+        }
+
+        for node in self.nodes.iter_mut().flatten() {
+            let mut attributes = renamer.rename_all(node.node_references_mut());
+            attributes.extend(renamer.rename_inline(node.parameters_mut()));
+
+            changes.extend(
+                attributes
+                    .into_iter()
+                    .map(|attribute| ValidationLocation::Node {
+                        name: node.name().to_string(),
+                        attribute: Some(attribute),
+                    }),
+            );
+        }
+
+        for parameter in self.parameters.iter_mut().flat_map(|p| p.iter_mut()) {
+            let references = parameter
+                .node_references_mut()
+                .into_iter()
+                .map(|(attribute, reference)| (attribute, vec![reference]))
+                .collect();
+            let mut attributes = renamer.rename_all(references);
+            attributes.extend(renamer.rename_inline(parameter.parameters_mut()));
+
+            changes.extend(
+                attributes
+                    .into_iter()
+                    .map(|attribute| ValidationLocation::Parameter {
+                        name: parameter.name().unwrap_or_default().to_string(),
+                        attribute: Some(attribute),
+                    }),
+            );
+        }
+
+        for recorder in self.recorders.iter_mut().flat_map(|r| r.iter_mut()) {
+            let mut attributes = renamer.rename_all(recorder.node_references_mut());
+            attributes.extend(renamer.rename_inline(recorder.parameters_mut()));
+
+            changes.extend(
+                attributes
+                    .into_iter()
+                    .map(|attribute| ValidationLocation::Recorder {
+                        name: recorder.name().to_string(),
+                        attribute: Some(attribute),
+                    }),
+            );
+        }
+
+        Ok(changes)
+    }
+
+    /// Rename the parameter `old` to `new`, updating all references to it.
+    ///
+    /// References are updated in nodes, other parameters and recorders, including those
+    /// within inline parameters and the `parameters` of a `TablesRecorder`. References from
+    /// custom components are not known, and so are not updated.
+    ///
+    /// Returns the locations that were changed, starting with the renamed parameter itself. An
+    /// error is returned if there is no parameter named `old`, or the name `new` is already
+    /// used by a parameter in the network's [`ParameterGraph`](crate::parameters::ParameterGraph)
+    /// (including inline parameters, which Pywr names after their owner).
+    pub fn rename_parameter(
+        &mut self,
+        old: &str,
+        new: &str,
+    ) -> Result<Vec<ValidationLocation>, PywrSchemaError> {
+        if self.parameter_graph().get_parameter(new).is_some() {
+            return Err(PywrSchemaError::NameAlreadyExists {
+                component: "parameter",
+                name: new.to_string(),
+            });
+        }
+
+        let parameter = self
+            .parameters
+            .iter_mut()
+            .flat_map(|p| p.iter_mut())
+            .find(|p| p.name() == Some(old))
+            .ok_or_else(|| PywrSchemaError::ComponentNotFound {
+                component: "parameter",
+                name: old.to_string(),
+            })?;
+
+        parameter.set_name(Some(new));
+        let mut changes = vec![ValidationLocation::Parameter {
+            name: new.to_string(),
+            attribute: None,
+        }];
+
+        for node in self.nodes.iter_mut().flatten() {
+            changes.extend(
+                rename_references(old, new, node.parameters_mut())
+                    .into_iter()
+                    .map(|attribute| ValidationLocation::Node {
+                        name: node.name().to_string(),
+                        attribute: Some(attribute),
+                    }),
+            );
+        }
+
+        for parameter in self.parameters.iter_mut().flat_map(|p| p.iter_mut()) {
+            changes.extend(
+                rename_references(old, new, parameter.parameters_mut())
+                    .into_iter()
+                    .map(|attribute| ValidationLocation::Parameter {
+                        name: parameter.name().unwrap_or_default().to_string(),
+                        attribute: Some(attribute),
+                    }),
+            );
+        }
+
+        for recorder in self.recorders.iter_mut().flat_map(|r| r.iter_mut()) {
+            let mut attributes = rename_references(old, new, recorder.parameters_mut());
+
+            if let Recorder::Core(CoreRecorder::Tables(r)) = recorder {
+                for (i, entry) in r.parameters.iter_mut().flatten().enumerate() {
+                    if entry.name() == old {
+                        *entry.name_mut() = new.to_string();
+                        attributes.push(format!("parameters[{i}]"));
+                    }
+                }
+            }
+
+            changes.extend(
+                attributes
+                    .into_iter()
+                    .map(|attribute| ValidationLocation::Recorder {
+                        name: recorder.name().to_string(),
+                        attribute: Some(attribute),
+                    }),
+            );
+        }
+
+        Ok(changes)
+    }
+}
+
+/// Update the references to the parameter `old` in `values`, returning the paths of the
+/// values that were changed.
+fn rename_references(
+    old: &str,
+    new: &str,
+    values: HashMap<&str, ParameterValueTypeMut>,
+) -> Vec<String> {
+    let mut attributes = Vec::new();
+
+    visit_parameter_values_mut(values, &mut |path, value| {
+        if let ParameterValue::Reference(name) = value
+            && name == old
+        {
+            *name = new.to_string();
+            attributes.push(path.to_string());
+        }
+    });
+
+    attributes
+}
+
+struct NodeRenamer<'a> {
+    old: &'a str,
+    new: &'a str,
+    /// The node before renaming, used to identify references to its sub-nodes.
+    node: Node,
+    /// The names of the network's nodes before renaming.
+    names: HashSet<String>,
+}
+
+impl NodeRenamer<'_> {
+    /// Update `reference` if it refers to the renamed node (or one of its sub-nodes),
+    /// returning true if it was changed.
+    fn rename(&self, reference: &mut String) -> bool {
+        if reference == self.old {
+            *reference = self.new.to_string();
+            return true;
+        }
+
+        // A reference to a node that is defined in the network is not a sub-node.
+        if !self.names.contains(reference.as_str())
+            && self.node.is_sub_node_name(reference)
+            && let Some(suffix) = reference.strip_prefix(self.old)
+        {
+            *reference = format!("{}{suffix}", self.new);
+            return true;
+        }
+
+        false
+    }
+
+    /// Update the given references, returning the sorted attributes that were changed.
+    fn rename_all(&self, references: HashMap<&str, Vec<&mut String>>) -> Vec<String> {
+        let mut attributes: Vec<String> = references
+            .into_iter()
+            .filter_map(|(attribute, references)| {
+                let mut changed = false;
+                for reference in references {
+                    changed |= self.rename(reference);
+                }
+                changed.then(|| attribute.to_string())
+            })
+            .collect();
+
+        attributes.sort();
+        attributes
+    }
+
+    /// Update the references of the inline parameters in `values`, returning the paths of
+    /// the attributes that were changed.
+    fn rename_inline(&self, values: HashMap<&str, ParameterValueTypeMut>) -> Vec<String> {
+        let mut attributes = Vec::new();
+
+        visit_parameter_values_mut(values, &mut |path, value| {
+            if let ParameterValue::Inline(parameter) = value {
+                let references = parameter
+                    .node_references_mut()
+                    .into_iter()
+                    .map(|(attribute, reference)| (attribute, vec![reference]))
+                    .collect();
+
+                attributes.extend(
+                    self.rename_all(references)
+                        .into_iter()
+                        .map(|attribute| format!("{path}.{attribute}")),
+                );
+            }
+        });
+
+        attributes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validation::ValidationLocation;
+    use crate::{PywrNetwork, PywrSchemaError};
+
+    #[test]
+    fn test_rename_node() {
+        let data = r#"
+            {
+                "nodes": [
+                    {"name": "supply1", "type": "Input"},
+                    {"name": "link1", "type": "LossLink", "loss_factor": 0.1},
+                    {
+                        "name": "demand1",
+                        "type": "Output",
+                        "max_flow": {"type": "max", "parameter": {"type": "flow", "node": "link1"}}
+                    },
+                    {"name": "agg", "type": "AggregatedNode", "nodes": ["link1", "demand1"]}
+                ],
+                "edges": [["supply1", "link1"], ["link1", "demand1"]],
+                "parameters": {
+                    "p1": {"type": "storagethreshold", "storage_node": "link1", "threshold": 0.5, "predicate": "LT"}
+                },
+                "recorders": {
+                    "r1": {"type": "NumpyArrayNodeRecorder", "node": "link1 Gross"}
+                }
+            }
+            "#;
+        let mut network: PywrNetwork = serde_json::from_str(data).unwrap();
+
+        let changes = network.rename_node("link1", "link2").unwrap();
+        let changes: Vec<String> = changes.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            changes,
+            vec![
+                "node `link2`",
+                "edge 0 (`supply1` -> `link2`)",
+                "edge 1 (`link2` -> `demand1`)",
+                "node `demand1` (attribute `max_flow.parameter.node`)",
+                "node `agg` (attribute `nodes`)",
+                "parameter `p1` (attribute `storage_node`)",
+                "recorder `r1` (attribute `node`)",
+            ]
+        );
+
+        let json = serde_json::to_string(&network).unwrap();
+        assert!(!json.contains("link1"));
+        assert!(json.contains(r#""node":"link2 Gross""#));
+        assert!(network.validate().is_empty());
+    }
+
+    #[test]
+    fn test_rename_node_errors() {
+        let data = r#"
+            {
+                "nodes": [
+                    {"name": "supply1", "type": "Input"},
+                    {"name": "demand1", "type": "Output"}
+                ],
+                "edges": [["supply1", "demand1"]]
+            }
+            "#;
+        let mut network: PywrNetwork = serde_json::from_str(data).unwrap();
+
+        assert!(matches!(
+            network.rename_node("supply1", "demand1"),
+            Err(PywrSchemaError::NameAlreadyExists { name, .. }) if name == "demand1"
+        ));
+        assert!(matches!(
+            network.rename_node("missing", "supply2"),
+            Err(PywrSchemaError::ComponentNotFound { name, .. }) if name == "missing"
+        ));
+
+        assert_eq!(
+            network.rename_node("supply1", "supply 1").unwrap(),
+            vec![
+                ValidationLocation::Node {
+                    name: "supply 1".to_string(),
+                    attribute: None
+                },
+                ValidationLocation::Edge {
+                    index: 0,
+                    from_node: "supply 1".to_string(),
+                    to_node: "demand1".to_string()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rename_parameter() {
+        let data = r#"
+            {
+                "nodes": [
+                    {"name": "supply1", "type": "Input", "max_flow": "p1"},
+                    {
+                        "name": "demand1",
+                        "type": "Output",
+                        "cost": {"type": "aggregated", "agg_func": "sum", "parameters": [-10, "p1"]}
+                    }
+                ],
+                "edges": [["supply1", "demand1"]],
+                "parameters": {
+                    "p1": {"type": "constant", "value": 5.0},
+                    "p2": {"type": "max", "parameter": "p1"}
+                },
+                "recorders": {
+                    "r1": {"type": "NumpyArrayParameterRecorder", "parameter": "p1"},
+                    "r2": {"type": "TablesRecorder", "url": "out.h5", "parameters": ["p2", "p1"]}
+                }
+            }
+            "#;
+        let mut network: PywrNetwork = serde_json::from_str(data).unwrap();
+
+        assert!(matches!(
+            network.rename_parameter("p1", "p2"),
+            Err(PywrSchemaError::NameAlreadyExists { name, .. }) if name == "p2"
+        ));
+        assert!(matches!(
+            network.rename_parameter("p1", "demand1.cost"),
+            Err(PywrSchemaError::NameAlreadyExists { .. })
+        ));
+        assert!(matches!(
+            network.rename_parameter("missing", "p3"),
+            Err(PywrSchemaError::ComponentNotFound { name, .. }) if name == "missing"
+        ));
+
+        let changes = network.rename_parameter("p1", "p3").unwrap();
+        let changes: Vec<String> = changes.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            changes,
+            vec![
+                "parameter `p3`",
+                "node `supply1` (attribute `max_flow`)",
+                "node `demand1` (attribute `cost.parameters[1]`)",
+                "parameter `p2` (attribute `parameter`)",
+                "recorder `r1` (attribute `parameter`)",
+                "recorder `r2` (attribute `parameters[1]`)",
+            ]
+        );
+
+        let json = serde_json::to_string(&network).unwrap();
+        assert!(!json.contains("p1"));
+        assert!(network.validate().is_empty());
+    }
+}
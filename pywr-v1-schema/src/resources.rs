@@ -0,0 +1,349 @@
+use crate::PywrSchemaError;
+use crate::model::{PywrModel, PywrNetwork};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+/// The URL schemes from which Pywr can read external resources.
+const URL_SCHEMES: [&str; 6] = ["http", "https", "ftp", "s3", "gs", "file"];
+
+/// An external resource referenced by a model, such as a table or the data of a parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resource {
+    /// A file on the local file system. Relative paths are resolved against the directory of
+    /// the model file.
+    Local(PathBuf),
+    /// A remote resource, given by its URL.
+    Url(String),
+}
+
+impl Resource {
+    /// Classify the resource `path`, as given in a model in the directory `dir`.
+    ///
+    /// Paths of the form `scheme://...` are URLs, except for `file://` URLs which are local
+    /// paths. An [`PywrSchemaError::InvalidUrlFound`] error is returned if the scheme of a URL
+    /// is not one that Pywr can read from, or nothing follows it.
+    pub fn new(path: &Path, dir: &Path) -> Result<Self, PywrSchemaError> {
+        let path_str = path.to_string_lossy();
+
+        let Some((scheme, rest)) = path_str.split_once("://") else {
+            return Ok(Self::Local(dir.join(path)));
+        };
+
+        let scheme = scheme.to_ascii_lowercase();
+        if !URL_SCHEMES.contains(&scheme.as_str()) || rest.is_empty() {
+            return Err(PywrSchemaError::InvalidUrlFound(path_str.to_string()));
+        }
+
+        match scheme.as_str() {
+            "file" => Ok(Self::Local(dir.join(rest))),
+            _ => Ok(Self::Url(path_str.to_string())),
+        }
+    }
+}
+
+impl PywrNetwork {
+    /// Check the external resources of this network, which was loaded from `path`.
+    ///
+    /// Each of the [`PywrNetwork::resource_paths`] is classified as a [`Resource`]. A
+    /// [`PywrSchemaError::LocalResourceNotFound`] error is returned for each local file that
+    /// does not exist, and a [`PywrSchemaError::InvalidUrlFound`] error for each invalid URL.
+    /// Remote resources are not checked.
+    ///
+    /// The errors are ordered by the resource path.
+    pub fn check_resources<P: AsRef<Path>>(&self, path: P) -> Vec<PywrSchemaError> {
+        let dir = path.as_ref().parent().unwrap_or_else(|| Path::new(""));
+
+        let mut paths: Vec<_> = self.resource_paths().into_iter().collect();
+        paths.sort();
+
+        paths
+            .iter()
+            .filter_map(|p| match Resource::new(p, dir) {
+                Ok(Resource::Local(local)) if !local.exists() => {
+                    Some(PywrSchemaError::LocalResourceNotFound(local))
+                }
+                Ok(_) => None,
+                Err(error) => Some(error),
+            })
+            .collect()
+    }
+
+    /// Rewrite the relative resource paths of this network, which was loaded from `from`, so
+    /// that they still resolve to the same files when it is saved to `to`.
+    ///
+    /// Absolute paths and URLs are left unchanged. The paths are rebased lexically, without
+    /// accessing the file system. A relative path cannot be rebased if only one of `from` and
+    /// `to` is absolute, or if `to` is above the directory of `from` in a way that can only be
+    /// resolved from the current directory (e.g. `../model.json`). Canonicalize both paths
+    /// first to avoid this.
+    ///
+    /// A [`PywrSchemaError::ResourceNotRebased`] error is returned for each path that could
+    /// not be rebased, which is left unchanged, and a [`PywrSchemaError::InvalidUrlFound`]
+    /// error for each invalid URL. The errors are ordered by the resource path.
+    pub fn rebase_resource_paths<P: AsRef<Path>, Q: AsRef<Path>>(
+        &mut self,
+        from: P,
+        to: Q,
+    ) -> Vec<PywrSchemaError> {
+        let from_dir = normalize(from.as_ref().parent().unwrap_or_else(|| Path::new("")));
+        let to_dir = normalize(to.as_ref().parent().unwrap_or_else(|| Path::new("")));
+        if from_dir == to_dir {
+            return Vec::new();
+        }
+
+        let mut paths: Vec<_> = self.resource_paths().into_iter().collect();
+        paths.sort();
+
+        let mut errors = Vec::new();
+        let mut new_paths = HashMap::new();
+        for path in paths {
+            if path.is_absolute() {
+                continue;
+            }
+            if path.to_string_lossy().contains("://") {
+                if let Err(error) = Resource::new(&path, &from_dir) {
+                    errors.push(error);
+                }
+                continue;
+            }
+
+            match relative_path(&normalize(&from_dir.join(&path)), &to_dir) {
+                Some(rebased) => {
+                    new_paths.insert(path, rebased);
+                }
+                None => errors.push(PywrSchemaError::ResourceNotRebased(path)),
+            }
+        }
+
+        self.update_resource_paths(&new_paths);
+        errors
+    }
+}
+
+impl PywrModel {
+    /// Check the external resources of this model, which was loaded from `path`.
+    ///
+    /// See [`PywrNetwork::check_resources`] for details.
+    pub fn check_resources<P: AsRef<Path>>(&self, path: P) -> Vec<PywrSchemaError> {
+        self.network.check_resources(path)
+    }
+
+    /// Rewrite the relative resource paths of this model, which was loaded from `from`, so
+    /// that they still resolve to the same files when it is saved to `to`.
+    ///
+    /// See [`PywrNetwork::rebase_resource_paths`] for details.
+    pub fn rebase_resource_paths<P: AsRef<Path>, Q: AsRef<Path>>(
+        &mut self,
+        from: P,
+        to: Q,
+    ) -> Vec<PywrSchemaError> {
+        self.network.rebase_resource_paths(from, to)
+    }
+}
+
+/// Lexically normalize `path`, removing `.` components and any `..` components that follow a
+/// named component.
+fn normalize(path: &Path) -> PathBuf {
+    let mut components: Vec<Component> = Vec::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match components.last() {
+                Some(Component::Normal(_)) => {
+                    components.pop();
+                }
+                // The parent of the root is itself.
+                Some(Component::RootDir) => {}
+                _ => components.push(component),
+            },
+            _ => components.push(component),
+        }
+    }
+
+    components.iter().collect()
+}
+
+/// Return the path of `target` relative to the directory `base`, both of which are
+/// normalized, using `/` as the separator. Returns [`None`] if there is no such path that can
+/// be determined lexically.
+fn relative_path(target: &Path, base: &Path) -> Option<PathBuf> {
+    if target.is_absolute() != base.is_absolute() {
+        return None;
+    }
+
+    let target: Vec<_> = target.components().collect();
+    let base: Vec<_> = base.components().collect();
+    let common = target.iter().zip(&base).take_while(|(t, b)| t == b).count();
+
+    let mut parts = Vec::new();
+    for component in &base[common..] {
+        match component {
+            Component::Normal(_) => parts.push("..".to_string()),
+            // The name of the directory above a `..` component is not known, and the paths
+            // cannot differ in their root or prefix (e.g. a Windows drive).
+            _ => return None,
+        }
+    }
+    for component in &target[common..] {
+        match component {
+            Component::Normal(name) => parts.push(name.to_string_lossy().to_string()),
+            Component::ParentDir => parts.push("..".to_string()),
+            _ => return None,
+        }
+    }
+
+    match parts.is_empty() {
+        true => Some(PathBuf::from(".")),
+        false => Some(PathBuf::from(parts.join("/"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Resource;
+    use crate::{PywrModel, PywrSchemaError};
+    use std::collections::HashSet;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_resource_kinds() {
+        let dir = Path::new("models");
+        let resource = |p: &str| Resource::new(Path::new(p), dir);
+
+        assert_eq!(
+            resource("data/flow.csv").unwrap(),
+            Resource::Local(PathBuf::from("models/data/flow.csv"))
+        );
+        assert_eq!(
+            resource("/data/flow.csv").unwrap(),
+            Resource::Local(PathBuf::from("/data/flow.csv"))
+        );
+        assert_eq!(
+            resource("file:///data/flow.csv").unwrap(),
+            Resource::Local(PathBuf::from("/data/flow.csv"))
+        );
+        assert_eq!(
+            resource("HTTPS://example.com/flow.csv").unwrap(),
+            Resource::Url("HTTPS://example.com/flow.csv".to_string())
+        );
+        assert!(matches!(
+            resource("htp://example.com/flow.csv"),
+            Err(PywrSchemaError::InvalidUrlFound(_))
+        ));
+        assert!(matches!(
+            resource("https://"),
+            Err(PywrSchemaError::InvalidUrlFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_resources() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+
+        fs::write(dir.join("found.csv"), "a,b\n1,2\n").unwrap();
+
+        let model = r#"
+        {
+            "metadata": {"title": "Resources"},
+            "timestepper": {"start": "2015-01-01", "end": "2015-12-31", "timestep": 1},
+            "nodes": [{"name": "supply1", "type": "Input"}],
+            "tables": {
+                "found": {"url": "found.csv"},
+                "missing": {"url": "missing.csv"},
+                "remote": {"url": "https://example.com/flow.csv"},
+                "invalid": {"url": "htp://example.com/flow.csv"}
+            }
+        }
+        "#;
+        let model: PywrModel = serde_json::from_str(model).unwrap();
+
+        let errors: Vec<_> = model
+            .check_resources(dir.join("model.json"))
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+
+        assert_eq!(
+            errors,
+            vec![
+                "An invalid URL was found: htp://example.com/flow.csv".to_string(),
+                format!(
+                    "Resource not found on local host: {}",
+                    dir.join("missing.csv").display()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rebase_resource_paths() {
+        let model = r#"
+        {
+            "metadata": {"title": "Rebase"},
+            "timestepper": {"start": "2015-01-01", "end": "2015-12-31", "timestep": 1},
+            "nodes": [
+                {
+                    "name": "supply1",
+                    "type": "Input",
+                    "max_flow": {"type": "dataframe", "url": "./data/flow.csv", "column": "a"}
+                }
+            ],
+            "tables": {
+                "shared": {"url": "../shared/demand.h5"},
+                "absolute": {"url": "/data/flow.csv"},
+                "remote": {"url": "https://example.com/flow.csv"},
+                "invalid": {"url": "htp://example.com/flow.csv"}
+            }
+        }
+        "#;
+        let model: PywrModel = serde_json::from_str(model).unwrap();
+
+        let mut rebased = model.clone();
+        let errors = rebased.rebase_resource_paths("models/a/model.json", "models/b/c/model.json");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], PywrSchemaError::InvalidUrlFound(_)));
+        assert_eq!(
+            rebased.resource_paths(),
+            HashSet::from([
+                PathBuf::from("../../a/data/flow.csv"),
+                PathBuf::from("../../shared/demand.h5"),
+                PathBuf::from("/data/flow.csv"),
+                PathBuf::from("https://example.com/flow.csv"),
+                PathBuf::from("htp://example.com/flow.csv"),
+            ])
+        );
+
+        // Moving the model back restores the paths, apart from the redundant `./`.
+        rebased.rebase_resource_paths("models/b/c/model.json", "models/a/model.json");
+        assert!(
+            rebased
+                .resource_paths()
+                .contains(Path::new("data/flow.csv"))
+        );
+        assert!(
+            rebased
+                .resource_paths()
+                .contains(Path::new("../shared/demand.h5"))
+        );
+
+        // The name of the current directory is not known, so paths within it cannot be rebased
+        // from a directory above it. Paths that are already above it can be.
+        let mut rebased = model.clone();
+        let errors: Vec<_> = rebased
+            .rebase_resource_paths("model.json", "../other/model.json")
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "Resource path could not be rebased: ./data/flow.csv",
+                "An invalid URL was found: htp://example.com/flow.csv",
+            ]
+        );
+        assert!(rebased == model);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::PywrSchemaError;
+use crate::model::{PywrModel, Scenario};
+
+impl Scenario {
+    /// Return the indices of this scenario that are run, after applying its slice.
+    ///
+    /// The slice is interpreted as the arguments of a Python `slice`: a single value is the
+    /// stop, two values are the start and stop, and three values are the start, stop and
+    /// step. Any of these may be `null`. No indices are returned if the step is zero.
+    pub fn indices(&self) -> Vec<usize> {
+        let (start, stop, step) = match self.slice.as_deref() {
+            None => (None, None, None),
+            Some([stop]) => (None, *stop, None),
+            Some([start, stop]) => (*start, *stop, None),
+            Some([start, stop, step, ..]) => (*start, *stop, *step),
+            Some([]) => (None, None, None),
+        };
+
+        let start = start.unwrap_or(0);
+        let stop = stop.unwrap_or(self.size).min(self.size);
+        match step.unwrap_or(1) {
+            0 => Vec::new(),
+            step => (start..stop).step_by(step).collect(),
+        }
+    }
+}
+
+impl PywrModel {
+    /// Return the combinations of scenario indices that the model will run, with one index
+    /// per scenario in the order the scenarios are defined.
+    ///
+    /// If `scenario_combinations` are given, they are returned as they are and the slices of
+    /// the scenarios are ignored, as in Pywr. Otherwise, every combination of the
+    /// [`Scenario::indices`] of each scenario is returned, varying the last scenario
+    /// fastest. A model without scenarios has a single, empty, combination.
+    ///
+    /// A [`PywrSchemaError::InvalidScenarioCombination`] error is returned for the first
+    /// user combination that does not have an index for each scenario, or has an index that
+    /// is out of range for its scenario's size.
+    pub fn scenario_index_combinations(&self) -> Result<Vec<Vec<usize>>, PywrSchemaError> {
+        let scenarios = self.scenarios.as_deref().unwrap_or_default();
+
+        if let Some(combinations) = &self.scenario_combinations {
+            for (index, combination) in combinations.iter().enumerate() {
+                if let Some(message) = check_combination(scenarios, combination) {
+                    return Err(PywrSchemaError::InvalidScenarioCombination { index, message });
+                }
+            }
+            return Ok(combinations.clone());
+        }
+
+        let mut combinations = vec![Vec::new()];
+        for scenario in scenarios {
+            let indices = scenario.indices();
+            combinations = combinations
+                .into_iter()
+                .flat_map(|combination| {
+                    indices.iter().map(move |i| {
+                        let mut combination = combination.clone();
+                        combination.push(*i);
+                        combination
+                    })
+                })
+                .collect();
+        }
+
+        Ok(combinations)
+    }
+}
+
+/// Return a description of why `combination` is not a valid combination of indices of
+/// `scenarios`, or [`None`] if it is valid.
+pub(crate) fn check_combination(scenarios: &[Scenario], combination: &[usize]) -> Option<String> {
+    if combination.len() != scenarios.len() {
+        return Some(format!(
+            "has {} indices, but there are {} scenarios",
+            combination.len(),
+            scenarios.len()
+        ));
+    }
+
+    scenarios
+        .iter()
+        .zip(combination)
+        .find(|(scenario, index)| **index >= scenario.size)
+        .map(|(scenario, index)| {
+            format!(
+                "index {index} is out of range for scenario `{}` of size {}",
+                scenario.name, scenario.size
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PywrModel;
+    use crate::model::Scenario;
+    use std::path::PathBuf;
+
+    fn scenario(size: usize, slice: Option<Vec<Option<usize>>>) -> Scenario {
+        Scenario {
+            name: "scenario".to_string(),
+            size,
+            slice,
+            ensemble_names: None,
+        }
+    }
+
+    #[test]
+    fn test_scenario_indices() {
+        assert_eq!(scenario(3, None).indices(), vec![0, 1, 2]);
+        assert_eq!(scenario(10, Some(vec![Some(2)])).indices(), vec![0, 1]);
+        assert_eq!(
+            scenario(10, Some(vec![Some(7), None])).indices(),
+            vec![7, 8, 9]
+        );
+        assert_eq!(
+            scenario(10, Some(vec![Some(0), None, Some(2)])).indices(),
+            vec![0, 2, 4, 6, 8]
+        );
+        assert_eq!(
+            scenario(4, Some(vec![Some(1), Some(20)])).indices(),
+            vec![1, 2, 3]
+        );
+        assert!(
+            scenario(4, Some(vec![None, None, Some(0)]))
+                .indices()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_scenario_index_combinations() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/models/scenario_with_user_combinations.json");
+        let mut model = PywrModel::from_path(path).unwrap();
+
+        assert_eq!(
+            model.scenario_index_combinations().unwrap(),
+            vec![vec![0, 0], vec![0, 1], vec![5, 1]]
+        );
+
+        model.scenario_combinations = Some(vec![vec![0, 2]]);
+        assert_eq!(
+            model.scenario_index_combinations().unwrap_err().to_string(),
+            "Scenario combination 0 is invalid: index 2 is out of range for scenario `scenario B` of size 2"
+        );
+
+        // Without user combinations the slices of each scenario are combined.
+        model.scenario_combinations = None;
+        assert_eq!(
+            model.scenario_index_combinations().unwrap(),
+            vec![vec![0, 0], vec![2, 0], vec![4, 0], vec![6, 0], vec![8, 0]]
+        );
+
+        model.scenarios = None;
+        assert_eq!(
+            model.scenario_index_combinations().unwrap(),
+            vec![Vec::<usize>::new()]
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use crate::PywrSchemaError;
+use crate::model::{PywrModel, PywrNetwork};
+use crate::nodes::{CoreNode, Node};
+use crate::parameters::{
+    CoreParameter, Parameter, ParameterValue, ParameterValueType, remove_suffix,
+    visit_parameter_values,
+};
+use crate::recorders::{CoreRecorder, Recorder};
+use crate::validation::ValidationLocation;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// A node, parameter or recorder whose type is a core Pywr type, but which failed to
+/// deserialize as that type and so was loaded as a custom component.
+///
+/// This is usually caused by a mistake in the component's attributes, such as a missing
+/// attribute or a value of the wrong type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CoreTypeMismatch {
+    /// The kind of component (`node`, `parameter` or `recorder`).
+    pub component: &'static str,
+    /// Where the component is defined. Inline parameters are located by the attribute of the
+    /// component that contains them.
+    pub location: ValidationLocation,
+    /// The type given in the model.
+    pub ty: String,
+    /// The path of the attribute that failed to deserialize, if the error is within one.
+    pub attribute: Option<String>,
+    /// The error from deserializing the component as its core type.
+    pub error: String,
+}
+
+impl fmt::Display for CoreTypeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: `{}` is a core {} type, but failed to deserialize",
+            self.location, self.ty, self.component
+        )?;
+        if let Some(attribute) = &self.attribute {
+            write!(f, " at `{attribute}`")?;
+        }
+        write!(f, ": {}", self.error)
+    }
+}
+
+impl PywrModel {
+    /// Load a model from a file path, returning an error if any node, parameter or recorder
+    /// has a core type but could not be deserialized as that type.
+    ///
+    /// See [`PywrNetwork::core_type_mismatches`] for details.
+    pub fn from_path_strict<P: AsRef<Path>>(path: P) -> Result<Self, PywrSchemaError> {
+        let model = Self::from_path(path)?;
+
+        let mismatches = model.network.core_type_mismatches();
+        if !mismatches.is_empty() {
+            return Err(PywrSchemaError::CoreTypeMismatch(mismatches));
+        }
+
+        Ok(model)
+    }
+}
+
+impl PywrNetwork {
+    /// Find the custom nodes, parameters (including inline parameters) and recorders whose
+    /// type is a core type.
+    ///
+    /// A component that fails to deserialize as a core type is loaded as a custom component
+    /// instead, which hides any mistakes in its definition. Each such component is
+    /// deserialized again as its core type to report the underlying error. Parameter and
+    /// recorder types are matched in the same way as when they are loaded; for example, the
+    /// type of a named parameter is case-insensitive and may end in `parameter`.
+    pub fn core_type_mismatches(&self) -> Vec<CoreTypeMismatch> {
+        let mut mismatches = Vec::new();
+
+        for node in self.nodes.iter().flatten() {
+            let location = |attribute: Option<&str>| ValidationLocation::Node {
+                name: node.name().to_string(),
+                attribute: attribute.map(str::to_string),
+            };
+
+            match node {
+                Node::Core(n) => check_inline_parameters(n.parameters(), location, &mut mismatches),
+                Node::Custom(n) => mismatches.extend(check(
+                    CoreNode::deserialize_variant,
+                    "node",
+                    location(None),
+                    &n.ty,
+                    &n.ty,
+                    n,
+                    None,
+                )),
+            }
+        }
+
+        for parameter in self.parameters.iter().flat_map(|p| p.iter()) {
+            let name = parameter.name().unwrap_or_default();
+            let location = |attribute: Option<&str>| ValidationLocation::Parameter {
+                name: name.to_string(),
+                attribute: attribute.map(str::to_string),
+            };
+
+            match parameter {
+                Parameter::Core(_) => {
+                    check_inline_parameters(parameter.parameters(), location, &mut mismatches)
+                }
+                Parameter::Custom(p) => mismatches.extend(check(
+                    CoreParameter::deserialize_variant,
+                    "parameter",
+                    location(None),
+                    &p.ty,
+                    remove_suffix(&p.ty.to_lowercase(), "parameter"),
+                    p,
+                    Some(name),
+                )),
+            }
+        }
+
+        for recorder in self.recorders.iter().flat_map(|r| r.iter()) {
+            let location = |attribute: Option<&str>| ValidationLocation::Recorder {
+                name: recorder.name().to_string(),
+                attribute: attribute.map(str::to_string),
+            };
+
+            match recorder {
+                Recorder::Core(_) => {
+                    check_inline_parameters(recorder.parameters(), location, &mut mismatches)
+                }
+                Recorder::Custom(r) => mismatches.extend(check(
+                    CoreRecorder::deserialize_variant,
+                    "recorder",
+                    location(None),
+                    &r.ty,
+                    remove_suffix(&r.ty.to_lowercase(), "recorder"),
+                    r,
+                    Some(recorder.name()),
+                )),
+            }
+        }
+
+        mismatches
+    }
+}
+
+/// The `deserialize_variant` method of a core type.
+type DeserializeVariant<T> =
+    fn(&str, Value) -> Option<Result<T, serde_path_to_error::Error<serde_json::Error>>>;
+
+/// Check the inline custom parameters within `values`, which are located by `location`.
+fn check_inline_parameters<'a, F>(
+    values: HashMap<&str, ParameterValueType<'a>>,
+    location: F,
+    mismatches: &mut Vec<CoreTypeMismatch>,
+) where
+    F: Fn(Option<&str>) -> ValidationLocation,
+{
+    visit_parameter_values(values, &mut |path, value| {
+        if let ParameterValue::Inline(parameter) = value
+            && let Parameter::Custom(p) = parameter.as_ref()
+        {
+            // Inline parameters are deserialized with the exact tags of the core parameters.
+            mismatches.extend(check(
+                CoreParameter::deserialize_variant,
+                "parameter",
+                location(Some(path)),
+                &p.ty,
+                &p.ty,
+                p,
+                None,
+            ));
+        }
+    });
+}
+
+/// Deserialize a custom component as the core type `ty` using `deserialize`, returning the
+/// error if `ty` is a core type. Components that are keyed by name in the model do not
+/// serialize their name, so it must be given as `name`.
+fn check<T>(
+    deserialize: DeserializeVariant<T>,
+    component: &'static str,
+    location: ValidationLocation,
+    original_ty: &str,
+    ty: &str,
+    custom: &impl Serialize,
+    name: Option<&str>,
+) -> Option<CoreTypeMismatch> {
+    let mut value = serde_json::to_value(custom).expect("Custom components can be serialised.");
+    if let Value::Object(attributes) = &mut value {
+        attributes.remove("type");
+        if let Some(name) = name {
+            attributes.insert("name".to_string(), Value::String(name.to_string()));
+        }
+    }
+
+    let error = deserialize(ty, value)?.err()?;
+    let attribute = error.path().to_string();
+
+    Some(CoreTypeMismatch {
+        component,
+        location,
+        ty: original_ty.to_string(),
+        attribute: (attribute != ".").then_some(attribute),
+        error: error.into_inner().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PywrModel;
+    use crate::validation::ValidationLocation;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn test_model_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("models")
+    }
+
+    /// None of the test models should have components that fall back to a custom type,
+    /// except for those using external data that is not yet supported by the schema.
+    #[test]
+    fn test_pywr_models_have_no_mismatches() {
+        let expected: HashMap<&str, Vec<&str>> = [
+            (
+                "reservoir_evaporation_areafromfile.json",
+                vec!["parameter `reservoir_area`"],
+            ),
+            (
+                "reservoir_initial_vol_from_table.json",
+                vec!["node `supply1`", "node `supply2`"],
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        for entry in fs::read_dir(test_model_dir()).unwrap() {
+            let path = entry.unwrap().path();
+            // `extra2.json` is a network included by `extra1.json` rather than a model.
+            if path.extension().is_none_or(|ext| ext != "json") || path.ends_with("extra2.json") {
+                continue;
+            }
+
+            let file_name = path.file_name().unwrap().to_str().unwrap();
+            match expected.get(file_name) {
+                Some(locations) => {
+                    let model = PywrModel::from_path(&path).unwrap();
+                    let mismatches: Vec<_> = model
+                        .network
+                        .core_type_mismatches()
+                        .iter()
+                        .map(|m| m.location.to_string())
+                        .collect();
+                    assert_eq!(&mismatches, locations);
+                    assert!(PywrModel::from_path_strict(&path).is_err());
+                }
+                None => {
+                    let model = PywrModel::from_path_strict(&path);
+                    assert!(model.is_ok(), "{path:?} failed to load: {:?}", model.err());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_core_type_mismatches() {
+        let data = r#"
+        {
+            "metadata": {"title": "Mismatches", "minimum_version": "0.1"},
+            "timestepper": {"start": "2015-01-01", "end": "2015-12-31", "timestep": 1},
+            "nodes": [
+                {"name": "supply1", "type": "Input", "max_flow": "profile"},
+                {
+                    "name": "link1",
+                    "type": "link",
+                    "max_flow": {"type": "constant", "value": "ten"},
+                    "cost": 1.0
+                },
+                {"name": "demand1", "type": "Output", "cost": -10, "max_flow": [1.0]}
+            ],
+            "edges": [["supply1", "link1"], ["link1", "demand1"]],
+            "parameters": {
+                "profile": {"type": "MonthlyProfileParameter", "values": [1.0, 2.0]},
+                "my-param": {"type": "mycustom", "value": 1.0}
+            }
+        }
+        "#;
+
+        let model: PywrModel = serde_json::from_str(data).unwrap();
+        let mismatches = model.network.core_type_mismatches();
+
+        let messages: Vec<_> = mismatches.iter().map(|m| m.to_string()).collect();
+        assert_eq!(mismatches.len(), 3, "{messages:#?}");
+
+        assert_eq!(
+            mismatches[0].location,
+            ValidationLocation::Node {
+                name: "link1".to_string(),
+                attribute: Some("max_flow".to_string()),
+            }
+        );
+        assert_eq!(mismatches[0].attribute.as_deref(), Some("value"));
+
+        assert_eq!(mismatches[1].component, "node");
+        assert_eq!(mismatches[1].ty, "Output");
+        assert_eq!(mismatches[1].attribute.as_deref(), Some("max_flow"));
+
+        assert_eq!(
+            mismatches[2].location,
+            ValidationLocation::Parameter {
+                name: "profile".to_string(),
+                attribute: None,
+            }
+        );
+        assert_eq!(mismatches[2].attribute.as_deref(), Some("values"));
+        assert!(
+            messages[2].starts_with(
+                "parameter `profile`: `MonthlyProfileParameter` is a core parameter type, \
+                but failed to deserialize at `values`: "
+            ),
+            "{}",
+            messages[2]
+        );
+    }
+}
@@ -10,11 +10,13 @@ use std::ops::{Deref, DerefMut};
 use std::path::PathBuf;
 use std::vec::IntoIter;
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Table {
     // Do not serialize name on the object as it is used as the key in the parent map,
     // and we don't want to duplicate it in the output.
     #[serde(skip_serializing)]
+    #[cfg_attr(feature = "json-schema", schemars(skip))]
     pub name: String,
     pub url: PathBuf,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -45,7 +47,7 @@ impl Table {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Default)]
 pub struct TableVec(Vec<Table>);
 
 impl TableVec {
@@ -0,0 +1,348 @@
+use crate::PywrSchemaError;
+use crate::model::{DateType, Timestep, Timestepper};
+use chrono::{Datelike, Months, NaiveDateTime, NaiveTime, TimeDelta, Timelike, Weekday};
+use std::fmt;
+use std::str::FromStr;
+
+/// The weekday anchors of weekly frequencies, as used by pandas (e.g. `W-MON`).
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("MON", Weekday::Mon),
+    ("TUE", Weekday::Tue),
+    ("WED", Weekday::Wed),
+    ("THU", Weekday::Thu),
+    ("FRI", Weekday::Fri),
+    ("SAT", Weekday::Sat),
+    ("SUN", Weekday::Sun),
+];
+
+/// The frequency of a model's timesteps.
+///
+/// This is parsed from a pandas offset alias, optionally prefixed by a multiple (e.g. `7D`).
+/// Each timestep is a period that covers `n` units of the frequency, and the first timestep
+/// is the period that contains the start of the timestepper, as with `pandas.period_range`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    /// Every `n` hours (`H` or `h`), starting on the hour.
+    Hours(u32),
+    /// Every `n` days (`D`), starting at midnight.
+    Days(u32),
+    /// Every `n` weeks, ending on the `anchor` day (`W-MON`, ..., `W-SUN`). `W` is the same
+    /// as `W-SUN`.
+    Weeks { n: u32, anchor: Weekday },
+    /// Every `n` months, labelled by the end of the month (`M` or `ME`).
+    MonthEnd(u32),
+    /// Every `n` months, labelled by the start of the month (`MS`).
+    MonthStart(u32),
+}
+
+impl FromStr for Frequency {
+    type Err = PywrSchemaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || PywrSchemaError::InvalidFrequency(s.to_string());
+
+        let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (n, alias) = s.split_at(split);
+        let n = match n {
+            "" => 1,
+            n => n.parse().map_err(|_| invalid())?,
+        };
+        if n == 0 {
+            return Err(invalid());
+        }
+
+        let frequency = match alias {
+            "H" | "h" => Self::Hours(n),
+            "D" => Self::Days(n),
+            "W" => Self::Weeks {
+                n,
+                anchor: Weekday::Sun,
+            },
+            "M" | "ME" => Self::MonthEnd(n),
+            "MS" => Self::MonthStart(n),
+            _ => {
+                let anchor = alias.strip_prefix("W-").ok_or_else(invalid)?;
+                let (_, anchor) = WEEKDAYS
+                    .iter()
+                    .find(|(name, _)| *name == anchor)
+                    .ok_or_else(invalid)?;
+                Self::Weeks { n, anchor: *anchor }
+            }
+        };
+
+        Ok(frequency)
+    }
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (n, alias) = match self {
+            Self::Hours(n) => (n, "H".to_string()),
+            Self::Days(n) => (n, "D".to_string()),
+            Self::Weeks { n, anchor } => {
+                let (name, _) = WEEKDAYS.iter().find(|(_, day)| day == anchor).unwrap();
+                (n, format!("W-{name}"))
+            }
+            Self::MonthEnd(n) => (n, "M".to_string()),
+            Self::MonthStart(n) => (n, "MS".to_string()),
+        };
+
+        match n {
+            1 => write!(f, "{alias}"),
+            n => write!(f, "{n}{alias}"),
+        }
+    }
+}
+
+impl Frequency {
+    /// Return the start of the period of this frequency that contains `time`.
+    fn period_start(&self, time: NaiveDateTime) -> NaiveDateTime {
+        let midnight = time.date().and_time(NaiveTime::MIN);
+
+        match self {
+            Self::Hours(_) => midnight + TimeDelta::hours(time.hour() as i64),
+            Self::Days(_) => midnight,
+            Self::Weeks { anchor, .. } => {
+                // Weeks start on the day after the anchor.
+                let start = anchor.succ().num_days_from_monday() as i64;
+                let days = (time.weekday().num_days_from_monday() as i64 - start).rem_euclid(7);
+                midnight - TimeDelta::days(days)
+            }
+            Self::MonthEnd(_) | Self::MonthStart(_) => midnight.with_day(1).unwrap(),
+        }
+    }
+
+    /// Return the start of the period after the one starting at `start`, or [`None`] if it is
+    /// out of range.
+    fn next_start(&self, start: NaiveDateTime) -> Option<NaiveDateTime> {
+        match *self {
+            Self::Hours(n) => start.checked_add_signed(TimeDelta::hours(n as i64)),
+            Self::Days(n) => start.checked_add_signed(TimeDelta::days(n as i64)),
+            Self::Weeks { n, .. } => start.checked_add_signed(TimeDelta::weeks(n as i64)),
+            Self::MonthEnd(n) | Self::MonthStart(n) => start.checked_add_months(Months::new(n)),
+        }
+    }
+}
+
+/// A single timestep, which covers the time from `start` up to, but not including, `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimestepPeriod {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+impl TimestepPeriod {
+    /// Return the length of the timestep.
+    pub fn duration(&self) -> TimeDelta {
+        self.end - self.start
+    }
+}
+
+/// An iterator over the timesteps of a [`Timestepper`].
+#[derive(Debug, Clone)]
+pub struct Timesteps {
+    frequency: Frequency,
+    next: Option<NaiveDateTime>,
+    end: NaiveDateTime,
+}
+
+impl Iterator for Timesteps {
+    type Item = TimestepPeriod;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.next.filter(|start| *start <= self.end)?;
+        let end = self.frequency.next_start(start)?;
+        self.next = Some(end);
+
+        Some(TimestepPeriod { start, end })
+    }
+}
+
+impl fmt::Display for DateType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Date(date) => write!(f, "{date}"),
+            Self::DateTime(date_time) => write!(f, "{date_time}"),
+        }
+    }
+}
+
+impl DateType {
+    /// Return the date and time, using midnight for a date.
+    pub fn date_time(&self) -> NaiveDateTime {
+        match self {
+            Self::Date(date) => date.and_time(NaiveTime::MIN),
+            Self::DateTime(date_time) => *date_time,
+        }
+    }
+}
+
+impl Timestep {
+    /// Return the frequency of the timestep. An integer timestep is a number of days.
+    ///
+    /// An [`PywrSchemaError::InvalidFrequency`] error is returned if the timestep is zero, or
+    /// the frequency is not one of those supported by [`Frequency`].
+    pub fn frequency(&self) -> Result<Frequency, PywrSchemaError> {
+        match self {
+            Self::Days(days) => match u32::try_from(*days) {
+                Ok(days) if days > 0 => Ok(Frequency::Days(days)),
+                _ => Err(PywrSchemaError::InvalidFrequency(days.to_string())),
+            },
+            Self::Frequency(frequency) => frequency.parse(),
+        }
+    }
+}
+
+impl Timestepper {
+    /// Return an iterator over the timesteps, from the one containing `start` to the one
+    /// containing `end`.
+    ///
+    /// An error is returned if the frequency is invalid, or `start` is after `end`.
+    pub fn timesteps(&self) -> Result<Timesteps, PywrSchemaError> {
+        let frequency = self.timestep.frequency()?;
+        if self.start.date_time() > self.end.date_time() {
+            return Err(PywrSchemaError::TimestepperStartAfterEnd {
+                start: self.start,
+                end: self.end,
+            });
+        }
+
+        Ok(Timesteps {
+            frequency,
+            next: Some(frequency.period_start(self.start.date_time())),
+            end: self.end.date_time(),
+        })
+    }
+
+    /// Return the number of timesteps.
+    ///
+    /// See [`Timestepper::timesteps`] for the errors that may be returned.
+    pub fn timestep_count(&self) -> Result<usize, PywrSchemaError> {
+        Ok(self.timesteps()?.count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Frequency, TimestepPeriod};
+    use crate::PywrSchemaError;
+    use crate::model::Timestepper;
+    use chrono::{NaiveDate, TimeDelta, Weekday};
+
+    fn timestepper(start: &str, end: &str, timestep: &str) -> Timestepper {
+        let data = format!(r#"{{"start": "{start}", "end": "{end}", "timestep": {timestep}}}"#);
+        serde_json::from_str(&data).unwrap()
+    }
+
+    #[test]
+    fn test_parse_frequency() {
+        let cases = [
+            ("D", Frequency::Days(1), "D"),
+            ("7D", Frequency::Days(7), "7D"),
+            ("H", Frequency::Hours(1), "H"),
+            ("6h", Frequency::Hours(6), "6H"),
+            (
+                "W",
+                Frequency::Weeks {
+                    n: 1,
+                    anchor: Weekday::Sun,
+                },
+                "W-SUN",
+            ),
+            (
+                "2W-MON",
+                Frequency::Weeks {
+                    n: 2,
+                    anchor: Weekday::Mon,
+                },
+                "2W-MON",
+            ),
+            ("M", Frequency::MonthEnd(1), "M"),
+            ("ME", Frequency::MonthEnd(1), "M"),
+            ("MS", Frequency::MonthStart(1), "MS"),
+        ];
+
+        for (s, frequency, display) in cases {
+            assert_eq!(s.parse::<Frequency>().unwrap(), frequency, "{s}");
+            assert_eq!(frequency.to_string(), display);
+        }
+
+        for s in ["", "0D", "Q", "W-FOO", "D7", "-1D"] {
+            assert!(
+                matches!(
+                    s.parse::<Frequency>(),
+                    Err(PywrSchemaError::InvalidFrequency(_))
+                ),
+                "{s}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_timesteps() {
+        assert_eq!(
+            timestepper("2015-01-01", "2015-12-31", "1")
+                .timestep_count()
+                .unwrap(),
+            365
+        );
+        assert_eq!(
+            timestepper("2015-01-01", "2015-12-31", "7")
+                .timestep_count()
+                .unwrap(),
+            53
+        );
+
+        // 2015-01-01 is a Thursday, so it is the last day of the first week.
+        let timesteps: Vec<_> = timestepper("2015-01-01", "2015-12-31", "\"W-THU\"")
+            .timesteps()
+            .unwrap()
+            .collect();
+        assert_eq!(timesteps.len(), 53);
+        assert_eq!(
+            timesteps[0],
+            TimestepPeriod {
+                start: NaiveDate::from_ymd_opt(2014, 12, 26)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+                end: NaiveDate::from_ymd_opt(2015, 1, 2)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+            }
+        );
+
+        let timesteps: Vec<_> = timestepper("2015-01-15T12:00:00", "2015-12-31", "\"M\"")
+            .timesteps()
+            .unwrap()
+            .collect();
+        assert_eq!(timesteps.len(), 12);
+        assert_eq!(timesteps[1].duration(), TimeDelta::days(28));
+
+        assert_eq!(
+            timestepper("2015-01-01T06:30:00", "2015-01-02", "\"H\"")
+                .timestep_count()
+                .unwrap(),
+            19
+        );
+    }
+
+    #[test]
+    fn test_invalid_timestepper() {
+        assert_eq!(
+            timestepper("2016-01-01", "2015-12-31", "1")
+                .timesteps()
+                .unwrap_err()
+                .to_string(),
+            "The timestepper start (2016-01-01) is after its end (2015-12-31)"
+        );
+        assert_eq!(
+            timestepper("2015-01-01", "2015-12-31", "0")
+                .timesteps()
+                .unwrap_err()
+                .to_string(),
+            "Invalid timestep frequency: `0`"
+        );
+    }
+}
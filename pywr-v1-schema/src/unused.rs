@@ -0,0 +1,226 @@
+use crate::inline::strings;
+use crate::model::PywrNetwork;
+use crate::nodes::Node;
+use crate::parameters::{Parameter, ParameterValue, ParameterValueType, visit_parameter_values};
+use crate::recorders::{CoreRecorder, FdcTarget, Recorder};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// The named parameters and tables of a network that are not used.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct UnusedComponents {
+    pub parameters: Vec<String>,
+    pub tables: Vec<String>,
+}
+
+impl UnusedComponents {
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty() && self.tables.is_empty()
+    }
+}
+
+impl PywrNetwork {
+    /// Return the named parameters and tables that are not used by the network.
+    ///
+    /// A parameter is used if it is referenced by a node or recorder, or by another used
+    /// parameter. A table is used if it is referenced by a node, recorder or used parameter.
+    /// Any string within the attributes of a custom component is assumed to be a reference,
+    /// as its references are not otherwise known.
+    ///
+    /// Parameters and tables are given in the order they are defined in the network.
+    pub fn unused_components(&self) -> UnusedComponents {
+        let mut usage = Usage::new(self);
+
+        for node in self.nodes.iter().flatten() {
+            usage.use_values(node.parameters());
+            if let Node::Custom(n) = node {
+                usage.use_attributes(&n.attributes);
+            }
+        }
+
+        for recorder in self.recorders.iter().flat_map(|r| r.iter()) {
+            usage.use_values(recorder.parameters());
+            match recorder {
+                Recorder::Core(CoreRecorder::Tables(r)) => {
+                    for entry in r.parameters.iter().flatten() {
+                        usage.use_parameter(entry.name());
+                    }
+                }
+                Recorder::Core(CoreRecorder::FlowDurationCurveDeviation(r)) => {
+                    for target in [&r.lower_target_fdc, &r.upper_target_fdc] {
+                        if let Some(FdcTarget::Table(table_ref)) = target {
+                            usage.tables.insert(table_ref.table.as_str());
+                        }
+                    }
+                }
+                Recorder::Custom(r) => usage.use_attributes(&r.attributes),
+                Recorder::Core(_) => {}
+            }
+        }
+
+        while let Some(parameter) = usage.pending.pop() {
+            usage.use_parameter_attributes(parameter);
+            usage.use_values(parameter.parameters());
+        }
+
+        UnusedComponents {
+            parameters: self
+                .parameters
+                .iter()
+                .flat_map(|p| p.iter())
+                .filter_map(|p| p.name())
+                .filter(|name| !usage.parameters.contains(name))
+                .map(|name| name.to_string())
+                .collect(),
+            tables: self
+                .tables
+                .iter()
+                .flat_map(|t| t.iter())
+                .map(|t| t.name.as_str())
+                .filter(|name| !usage.tables.contains(name))
+                .map(|name| name.to_string())
+                .collect(),
+        }
+    }
+
+    /// Remove the named parameters and tables that are not used by the network.
+    ///
+    /// See [`PywrNetwork::unused_components`] for when a parameter or table is used. Returns
+    /// the components that were removed.
+    pub fn prune_unused(&mut self) -> UnusedComponents {
+        let unused = self.unused_components();
+
+        if let Some(parameters) = &mut self.parameters {
+            parameters.retain(|p| {
+                !p.name()
+                    .is_some_and(|n| unused.parameters.iter().any(|u| u == n))
+            });
+            if parameters.is_empty() {
+                self.parameters = None;
+            }
+        }
+
+        if let Some(tables) = &mut self.tables {
+            tables.retain(|t| !unused.tables.contains(&t.name));
+            if tables.is_empty() {
+                self.tables = None;
+            }
+        }
+
+        unused
+    }
+}
+
+/// The parameters and tables found to be used while traversing a network.
+struct Usage<'a> {
+    named: HashMap<&'a str, &'a Parameter>,
+    parameters: HashSet<&'a str>,
+    tables: HashSet<&'a str>,
+    /// Used parameters whose own references are yet to be followed.
+    pending: Vec<&'a Parameter>,
+}
+
+impl<'a> Usage<'a> {
+    fn new(network: &'a PywrNetwork) -> Self {
+        let named = network
+            .parameters
+            .iter()
+            .flat_map(|p| p.iter())
+            .filter_map(|p| p.name().map(|name| (name, p)))
+            .collect();
+
+        Self {
+            named,
+            parameters: HashSet::new(),
+            tables: HashSet::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    fn use_parameter(&mut self, name: &'a str) {
+        if let Some(parameter) = self.named.get(name)
+            && self.parameters.insert(name)
+        {
+            self.pending.push(parameter);
+        }
+    }
+
+    /// Use the parameters and tables referenced by `values`, including within any inline
+    /// parameters.
+    fn use_values(&mut self, values: HashMap<&str, ParameterValueType<'a>>) {
+        visit_parameter_values(values, &mut |_, value| match value {
+            ParameterValue::Reference(name) => self.use_parameter(name),
+            ParameterValue::Table(table_ref) => {
+                self.tables.insert(table_ref.table.as_str());
+            }
+            ParameterValue::Inline(parameter) => self.use_parameter_attributes(parameter),
+            ParameterValue::Constant(_) => {}
+        });
+    }
+
+    /// Use the tables referenced directly by `parameter`, or anything referenced by a
+    /// custom parameter.
+    fn use_parameter_attributes(&mut self, parameter: &'a Parameter) {
+        self.tables
+            .extend(parameter.table_references().into_values());
+        if let Parameter::Custom(p) = parameter {
+            self.use_attributes(&p.attributes);
+        }
+    }
+
+    fn use_attributes(&mut self, attributes: &'a HashMap<String, Value>) {
+        for s in attributes.values().flat_map(strings) {
+            self.use_parameter(s);
+            self.tables.insert(s);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PywrNetwork;
+    use crate::unused::UnusedComponents;
+
+    #[test]
+    fn test_prune_unused() {
+        let data = r#"
+            {
+                "nodes": [
+                    {"name": "supply1", "type": "Input", "max_flow": {"type": "max", "parameter": "p1"}},
+                    {"name": "demand1", "type": "Output", "cost": {"table": "t1", "column": "a"}},
+                    {"name": "custom1", "type": "MyNode", "threshold": "p4"}
+                ],
+                "edges": [["supply1", "demand1"]],
+                "parameters": {
+                    "p1": {"type": "aggregated", "agg_func": "sum", "parameters": ["p2", 1.0]},
+                    "p2": {"type": "dataframe", "table": "t2"},
+                    "p3": {"type": "max", "parameter": "p5"},
+                    "p4": {"type": "constant", "value": 1.0},
+                    "p5": {"type": "dataframe", "table": "t3"},
+                    "p6": {"type": "constant", "value": 2.0}
+                },
+                "tables": {
+                    "t1": {"url": "t1.csv"},
+                    "t2": {"url": "t2.csv"},
+                    "t3": {"url": "t3.csv"},
+                    "t4": {"url": "t4.csv"}
+                },
+                "recorders": {
+                    "r1": {"type": "TablesRecorder", "url": "out.h5", "parameters": ["p6"]}
+                }
+            }
+            "#;
+        let mut network: PywrNetwork = serde_json::from_str(data).unwrap();
+
+        let expected = UnusedComponents {
+            parameters: vec!["p3".to_string(), "p5".to_string()],
+            tables: vec!["t3".to_string(), "t4".to_string()],
+        };
+        assert_eq!(network.unused_components(), expected);
+        assert_eq!(network.prune_unused(), expected);
+        assert!(network.unused_components().is_empty());
+        assert_eq!(network.parameters.as_ref().unwrap().len(), 4);
+        assert_eq!(network.tables.as_ref().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,1041 @@
+use crate::PywrSchemaError;
+use crate::model::{PywrModel, PywrMultiModel, PywrNetwork, Timestepper};
+use crate::nodes::{CoreNode, Node};
+use crate::parameters::{
+    CoreParameter, Parameter, ParameterValue, ParameterValueType, visit_parameter_values,
+};
+use crate::recorders::{CoreRecorder, FdcTarget, Recorder};
+use crate::scenarios::check_combination;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// The severity of a [`ValidationIssue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The model can be loaded, but is unlikely to behave as intended.
+    Warning,
+    /// The model is invalid and will fail to load in Pywr.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// The component of a network that a [`ValidationIssue`] relates to.
+///
+/// Where given, `attribute` is the path of the offending attribute within the component.
+/// Values nested within inline parameters are given as a dotted path (e.g.
+/// `max_flow.parameters[1]`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "component", rename_all = "lowercase")]
+pub enum ValidationLocation {
+    Node {
+        name: String,
+        attribute: Option<String>,
+    },
+    Edge {
+        index: usize,
+        from_node: String,
+        to_node: String,
+    },
+    Parameter {
+        name: String,
+        attribute: Option<String>,
+    },
+    Table {
+        name: String,
+    },
+    Recorder {
+        name: String,
+        attribute: Option<String>,
+    },
+    #[serde(rename = "sub_model")]
+    SubModel {
+        name: String,
+    },
+    Timestepper,
+    Scenario {
+        name: String,
+    },
+    #[serde(rename = "scenario_combination")]
+    ScenarioCombination {
+        index: usize,
+    },
+}
+
+impl fmt::Display for ValidationLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (component, name, attribute) = match self {
+            Self::Node { name, attribute } => ("node", name, attribute),
+            Self::Parameter { name, attribute } => ("parameter", name, attribute),
+            Self::Recorder { name, attribute } => ("recorder", name, attribute),
+            Self::Table { name } => ("table", name, &None),
+            Self::SubModel { name } => ("sub-model", name, &None),
+            Self::Scenario { name } => ("scenario", name, &None),
+            Self::Edge {
+                index,
+                from_node,
+                to_node,
+            } => return write!(f, "edge {index} (`{from_node}` -> `{to_node}`)"),
+            Self::Timestepper => return write!(f, "timestepper"),
+            Self::ScenarioCombination { index } => {
+                return write!(f, "scenario combination {index}");
+            }
+        };
+
+        match attribute {
+            Some(attribute) => write!(f, "{component} `{name}` (attribute `{attribute}`)"),
+            None => write!(f, "{component} `{name}`"),
+        }
+    }
+}
+
+/// A problem found when validating a network.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    pub location: ValidationLocation,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn error(location: ValidationLocation, message: String) -> Self {
+        Self {
+            severity: Severity::Error,
+            location,
+            message,
+        }
+    }
+
+    fn warning(location: ValidationLocation, message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            location,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.location, self.message)
+    }
+}
+
+impl PywrNetwork {
+    /// Check the references between the components of this network.
+    ///
+    /// This checks that edges connect existing, non-virtual nodes, and that the node,
+    /// parameter, table and recorder names referenced by nodes, parameters (including
+    /// inline parameters) and recorders exist. Duplicate names, circular parameter references
+    /// and nodes that are missing the connections they need to pass flow are also reported.
+    ///
+    /// The returned issues are ordered by the component in which they are found.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut validator = Validator::new(self);
+
+        validator.check_edges();
+        validator.check_nodes();
+        validator.check_parameters();
+        validator.check_recorders();
+        validator.check_parameter_cycles();
+
+        validator.issues
+    }
+}
+
+impl Timestepper {
+    /// Check that the timestep has a supported frequency, and that the timestepper does not
+    /// start after it ends.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        if let Err(PywrSchemaError::InvalidFrequency(frequency)) = self.timestep.frequency() {
+            issues.push(ValidationIssue::error(
+                ValidationLocation::Timestepper,
+                format!("invalid timestep frequency `{frequency}`"),
+            ));
+        }
+
+        if self.start.date_time() > self.end.date_time() {
+            issues.push(ValidationIssue::error(
+                ValidationLocation::Timestepper,
+                format!("starts ({}) after it ends ({})", self.start, self.end),
+            ));
+        }
+
+        issues
+    }
+}
+
+impl PywrModel {
+    /// Check the model's scenarios and scenario combinations.
+    pub(crate) fn check_scenarios(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let scenarios = self.scenarios.as_deref().unwrap_or_default();
+        let mut names = HashSet::new();
+
+        for scenario in scenarios {
+            let location = || ValidationLocation::Scenario {
+                name: scenario.name.clone(),
+            };
+
+            if !names.insert(scenario.name.as_str()) {
+                issues.push(ValidationIssue::error(
+                    location(),
+                    "duplicate scenario name".to_string(),
+                ));
+            }
+
+            if let Some(ensemble_names) = &scenario.ensemble_names
+                && ensemble_names.len() != scenario.size
+            {
+                issues.push(ValidationIssue::error(
+                    location(),
+                    format!(
+                        "has {} ensemble names, but a size of {}",
+                        ensemble_names.len(),
+                        scenario.size
+                    ),
+                ));
+            }
+
+            if scenario.size == 0 {
+                issues.push(ValidationIssue::error(
+                    location(),
+                    "has a size of zero".to_string(),
+                ));
+            } else if scenario.slice.is_none() {
+                continue;
+            } else if self.scenario_combinations.is_some() {
+                issues.push(ValidationIssue::warning(
+                    location(),
+                    "slice is ignored because `scenario_combinations` are given".to_string(),
+                ));
+            } else if scenario.slice.as_ref().and_then(|s| s.get(2)) == Some(&Some(0)) {
+                issues.push(ValidationIssue::error(
+                    location(),
+                    "slice step cannot be zero".to_string(),
+                ));
+            } else if scenario.indices().is_empty() {
+                issues.push(ValidationIssue::error(
+                    location(),
+                    format!("slice selects none of its {} indices", scenario.size),
+                ));
+            }
+        }
+
+        let mut seen = HashMap::new();
+        for (index, combination) in self.scenario_combinations.iter().flatten().enumerate() {
+            let location = ValidationLocation::ScenarioCombination { index };
+
+            if let Some(message) = check_combination(scenarios, combination) {
+                issues.push(ValidationIssue::error(location.clone(), message));
+            }
+            let first = *seen.entry(combination).or_insert(index);
+            if first != index {
+                issues.push(ValidationIssue::warning(
+                    location,
+                    format!("duplicates scenario combination {first}"),
+                ));
+            }
+        }
+
+        issues
+    }
+
+    /// Check the scenarios referenced by the parameters of the network, including inline
+    /// parameters, in the order nodes, parameters and then recorders.
+    pub(crate) fn check_scenario_references(&self) -> Vec<ValidationIssue> {
+        let sizes: HashMap<&str, usize> = self
+            .scenarios
+            .iter()
+            .flatten()
+            .map(|s| (s.name.as_str(), s.size))
+            .collect();
+
+        // Each parameter, with its owner and the prefix of its attributes within the owner.
+        let mut parameters: Vec<(Owner, String, &Parameter)> = Vec::new();
+        let inline = |owner, values| {
+            let mut inline = Vec::new();
+            visit_parameter_values(values, &mut |path, value| {
+                if let ParameterValue::Inline(parameter) = value {
+                    inline.push((owner, format!("{path}."), parameter.as_ref()));
+                }
+            });
+            inline
+        };
+
+        let network = &self.network;
+        for node in network.nodes.iter().flatten() {
+            parameters.extend(inline(Owner::Node(node.name()), node.parameters()));
+        }
+        for parameter in network.parameters.iter().flat_map(|p| p.iter()) {
+            let owner = Owner::Parameter(parameter.name().unwrap_or_default());
+            parameters.push((owner, String::new(), parameter));
+            parameters.extend(inline(owner, parameter.parameters()));
+        }
+        for recorder in network.recorders.iter().flat_map(|r| r.iter()) {
+            parameters.extend(inline(
+                Owner::Recorder(recorder.name()),
+                recorder.parameters(),
+            ));
+        }
+
+        let mut issues = Vec::new();
+        for (owner, prefix, parameter) in parameters {
+            let Parameter::Core(parameter) = parameter else {
+                continue;
+            };
+
+            // The scenario, and the attribute with one entry per scenario member and its length.
+            let (scenario, members) = match parameter {
+                CoreParameter::ConstantScenario(p) => {
+                    (&p.scenario, p.values.as_ref().map(|v| ("values", v.len())))
+                }
+                CoreParameter::ScenarioWrapper(p) => {
+                    (&p.scenario, Some(("parameters", p.parameters.len())))
+                }
+                CoreParameter::DataFrame(p) => match &p.scenario {
+                    Some(scenario) => (scenario, None),
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            let Some(size) = sizes.get(scenario.as_str()) else {
+                issues.push(ValidationIssue::error(
+                    owner.location(&format!("{prefix}scenario")),
+                    format!("references missing scenario `{scenario}`"),
+                ));
+                continue;
+            };
+
+            if let Some((attribute, len)) = members
+                && len != *size
+            {
+                issues.push(ValidationIssue::error(
+                    owner.location(&format!("{prefix}{attribute}")),
+                    format!(
+                        "has {len} {attribute}, but scenario `{scenario}` has a size of {size}"
+                    ),
+                ));
+            }
+        }
+
+        issues
+    }
+}
+
+impl PywrMultiModel {
+    /// Check the timestepper, and that each sub-model has a unique name, and either inline
+    /// `data` or a `filename` or `path` to load it from.
+    ///
+    /// Sub-models are not validated; see [`PywrNetwork::validate`].
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = self.timestepper.validate();
+        let mut names = HashSet::new();
+
+        for sub_model in &self.models {
+            let location = || ValidationLocation::SubModel {
+                name: sub_model.name.clone(),
+            };
+
+            if !names.insert(sub_model.name.as_str()) {
+                issues.push(ValidationIssue::error(
+                    location(),
+                    "duplicate sub-model name".to_string(),
+                ));
+            }
+
+            if sub_model.data.is_none() && sub_model.source_path().is_none() {
+                issues.push(ValidationIssue::error(
+                    location(),
+                    "has no `data`, `filename` or `path`".to_string(),
+                ));
+            }
+        }
+
+        issues
+    }
+}
+
+/// The component that owns a set of attributes being validated.
+#[derive(Clone, Copy)]
+enum Owner<'a> {
+    Node(&'a str),
+    Parameter(&'a str),
+    Recorder(&'a str),
+}
+
+impl Owner<'_> {
+    fn location(&self, attribute: &str) -> ValidationLocation {
+        let attribute = Some(attribute.to_string());
+        match self {
+            Self::Node(name) => ValidationLocation::Node {
+                name: name.to_string(),
+                attribute,
+            },
+            Self::Parameter(name) => ValidationLocation::Parameter {
+                name: name.to_string(),
+                attribute,
+            },
+            Self::Recorder(name) => ValidationLocation::Recorder {
+                name: name.to_string(),
+                attribute,
+            },
+        }
+    }
+}
+
+struct Validator<'a> {
+    network: &'a PywrNetwork,
+    nodes: HashMap<&'a str, &'a Node>,
+    parameters: HashSet<&'a str>,
+    tables: HashSet<&'a str>,
+    recorders: HashSet<&'a str>,
+    issues: Vec<ValidationIssue>,
+}
+
+impl<'a> Validator<'a> {
+    /// Index the names of the network's components, reporting any duplicates.
+    fn new(network: &'a PywrNetwork) -> Self {
+        let mut issues = Vec::new();
+
+        let mut nodes = HashMap::new();
+        for node in network.nodes.iter().flatten() {
+            if nodes.insert(node.name(), node).is_some() {
+                issues.push(ValidationIssue::error(
+                    ValidationLocation::Node {
+                        name: node.name().to_string(),
+                        attribute: None,
+                    },
+                    "duplicate node name".to_string(),
+                ));
+            }
+        }
+
+        let mut parameters = HashSet::new();
+        for name in network
+            .parameters
+            .iter()
+            .flat_map(|p| p.iter())
+            .filter_map(|p| p.name())
+        {
+            if !parameters.insert(name) {
+                issues.push(ValidationIssue::error(
+                    ValidationLocation::Parameter {
+                        name: name.to_string(),
+                        attribute: None,
+                    },
+                    "duplicate parameter name".to_string(),
+                ));
+            }
+        }
+
+        let mut tables = HashSet::new();
+        for table in network.tables.iter().flat_map(|t| t.iter()) {
+            if !tables.insert(table.name.as_str()) {
+                issues.push(ValidationIssue::error(
+                    ValidationLocation::Table {
+                        name: table.name.clone(),
+                    },
+                    "duplicate table name".to_string(),
+                ));
+            }
+        }
+
+        let mut recorders = HashSet::new();
+        for recorder in network.recorders.iter().flat_map(|r| r.iter()) {
+            if !recorders.insert(recorder.name()) {
+                issues.push(ValidationIssue::error(
+                    ValidationLocation::Recorder {
+                        name: recorder.name().to_string(),
+                        attribute: None,
+                    },
+                    "duplicate recorder name".to_string(),
+                ));
+            }
+        }
+
+        Self {
+            network,
+            nodes,
+            parameters,
+            tables,
+            recorders,
+            issues,
+        }
+    }
+
+    fn check_edges(&mut self) {
+        let mut incoming = HashSet::new();
+        let mut outgoing = HashSet::new();
+
+        for (index, edge) in self.network.edges.iter().flatten().enumerate() {
+            for node_name in [&edge.from_node, &edge.to_node] {
+                let message = match self.nodes.get(node_name.as_str()) {
+                    None => format!("references missing node `{node_name}`"),
+                    Some(node) if node.is_virtual() => {
+                        format!("connects virtual node `{node_name}`")
+                    }
+                    Some(_) => continue,
+                };
+
+                self.issues.push(ValidationIssue::error(
+                    ValidationLocation::Edge {
+                        index,
+                        from_node: edge.from_node.clone(),
+                        to_node: edge.to_node.clone(),
+                    },
+                    message,
+                ));
+            }
+
+            outgoing.insert(edge.from_node.as_str());
+            incoming.insert(edge.to_node.as_str());
+        }
+
+        for node in self.network.nodes.iter().flatten() {
+            // Custom nodes may be virtual, so their connections can not be checked.
+            let core_node = match node {
+                Node::Core(n) if !n.is_virtual() => n,
+                _ => continue,
+            };
+
+            let has_incoming = incoming.contains(node.name());
+            let has_outgoing = outgoing.contains(node.name());
+
+            let message = match core_node.as_ref() {
+                CoreNode::Input(_) | CoreNode::Catchment(_) if !has_outgoing => {
+                    "has no outgoing edges"
+                }
+                CoreNode::Input(_) | CoreNode::Catchment(_) => continue,
+                CoreNode::Output(_) if !has_incoming => "has no incoming edges",
+                CoreNode::Output(_) => continue,
+                CoreNode::Storage(_) | CoreNode::Reservoir(_) if !has_incoming && !has_outgoing => {
+                    "is not connected to any edges"
+                }
+                CoreNode::Storage(_) | CoreNode::Reservoir(_) => continue,
+                _ if !has_incoming => "has no incoming edges",
+                _ if !has_outgoing => "has no outgoing edges",
+                _ => continue,
+            };
+
+            self.issues.push(ValidationIssue::warning(
+                ValidationLocation::Node {
+                    name: node.name().to_string(),
+                    attribute: None,
+                },
+                message.to_string(),
+            ));
+        }
+    }
+
+    fn check_nodes(&mut self) {
+        for node in self.network.nodes.iter().flatten() {
+            let owner = Owner::Node(node.name());
+
+            let mut references: Vec<_> = node.node_references().into_iter().collect();
+            references.sort_by_key(|(attribute, _)| *attribute);
+            for (attribute, names) in references {
+                for name in names {
+                    self.check_node_reference(owner, attribute, name);
+                }
+            }
+
+            self.check_parameter_values(owner, node.parameters());
+        }
+    }
+
+    fn check_parameters(&mut self) {
+        for parameter in self.network.parameters.iter().flat_map(|p| p.iter()) {
+            let owner = Owner::Parameter(parameter.name().unwrap_or_default());
+
+            self.check_parameter_references(owner, "", parameter);
+            self.check_parameter_values(owner, parameter.parameters());
+        }
+    }
+
+    fn check_recorders(&mut self) {
+        for recorder in self.network.recorders.iter().flat_map(|r| r.iter()) {
+            let owner = Owner::Recorder(recorder.name());
+
+            let mut references: Vec<_> = recorder.node_references().into_iter().collect();
+            references.sort_by_key(|(attribute, _)| *attribute);
+            for (attribute, names) in references {
+                for name in names {
+                    self.check_node_reference(owner, attribute, name);
+                }
+            }
+
+            self.check_parameter_values(owner, recorder.parameters());
+
+            let core_recorder = match recorder {
+                Recorder::Core(r) => r,
+                Recorder::Custom(_) => continue,
+            };
+
+            match core_recorder {
+                CoreRecorder::Aggregated(r) => {
+                    for name in &r.recorders {
+                        if !self.recorders.contains(name.as_str()) {
+                            self.issues.push(ValidationIssue::error(
+                                owner.location("recorders"),
+                                format!("references missing recorder `{name}`"),
+                            ));
+                        }
+                    }
+                }
+                CoreRecorder::Tables(r) => {
+                    for entry in r.parameters.iter().flatten() {
+                        if !self.parameters.contains(entry.name()) {
+                            self.issues.push(ValidationIssue::error(
+                                owner.location("parameters"),
+                                format!("references missing parameter `{}`", entry.name()),
+                            ));
+                        }
+                    }
+                }
+                CoreRecorder::FlowDurationCurveDeviation(r) => {
+                    let targets = [
+                        ("lower_target_fdc", &r.lower_target_fdc),
+                        ("upper_target_fdc", &r.upper_target_fdc),
+                    ];
+                    for (attribute, target) in targets {
+                        if let Some(FdcTarget::Table(table_ref)) = target {
+                            self.check_table_reference(owner, attribute, &table_ref.table);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Return true if `name` is a node, or a sub-node that Pywr creates for a node.
+    ///
+    /// Some nodes (e.g. `LossLink`) create sub-nodes in Pywr that are named after the
+    /// parent (e.g. `link1 Gross`). These are not defined in the network, so are checked
+    /// with [`Node::is_sub_node_name`].
+    fn node_exists(&self, name: &str) -> bool {
+        if self.nodes.contains_key(name) {
+            return true;
+        }
+
+        name.match_indices(' ').any(|(i, _)| {
+            self.nodes
+                .get(&name[..i])
+                .is_some_and(|n| n.is_sub_node_name(name))
+        })
+    }
+
+    fn check_parameter_cycles(&mut self) {
+        for cycle in self.network.parameter_graph().cycles() {
+            self.issues.push(ValidationIssue::error(
+                ValidationLocation::Parameter {
+                    name: cycle[0].to_string(),
+                    attribute: None,
+                },
+                format!("circular parameter reference: {}", cycle.join(" -> ")),
+            ));
+        }
+    }
+
+    fn check_node_reference(&mut self, owner: Owner, attribute: &str, name: &str) {
+        if !self.node_exists(name) {
+            self.issues.push(ValidationIssue::error(
+                owner.location(attribute),
+                format!("references missing node `{name}`"),
+            ));
+        }
+    }
+
+    fn check_table_reference(&mut self, owner: Owner, attribute: &str, name: &str) {
+        if !self.tables.contains(name) {
+            self.issues.push(ValidationIssue::error(
+                owner.location(attribute),
+                format!("references missing table `{name}`"),
+            ));
+        }
+    }
+
+    /// Check the node and table references of `parameter`, whose attributes are located
+    /// under `prefix` within `owner`.
+    fn check_parameter_references(&mut self, owner: Owner, prefix: &str, parameter: &Parameter) {
+        let mut node_references: Vec<_> = parameter.node_references().into_iter().collect();
+        node_references.sort_by_key(|(attribute, _)| *attribute);
+        for (attribute, name) in node_references {
+            self.check_node_reference(owner, &format!("{prefix}{attribute}"), name);
+        }
+
+        let mut table_references: Vec<_> = parameter.table_references().into_iter().collect();
+        table_references.sort_by_key(|(attribute, _)| *attribute);
+        for (attribute, name) in table_references {
+            self.check_table_reference(owner, &format!("{prefix}{attribute}"), name);
+        }
+    }
+
+    /// Check the parameter values of `owner`, including any nested inline parameters.
+    fn check_parameter_values(
+        &mut self,
+        owner: Owner,
+        values: HashMap<&str, ParameterValueType<'a>>,
+    ) {
+        let mut visited = Vec::new();
+        visit_parameter_values(values, &mut |path, value| {
+            visited.push((path.to_string(), value));
+        });
+
+        for (path, value) in visited {
+            match value {
+                ParameterValue::Reference(name) => {
+                    if !self.parameters.contains(name.as_str()) {
+                        self.issues.push(ValidationIssue::error(
+                            owner.location(&path),
+                            format!("references missing parameter `{name}`"),
+                        ));
+                    }
+                }
+                ParameterValue::Table(table_ref) => {
+                    self.check_table_reference(owner, &path, &table_ref.table)
+                }
+                ParameterValue::Inline(parameter) => {
+                    self.check_parameter_references(owner, &format!("{path}."), parameter)
+                }
+                ParameterValue::Constant(_) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validation::{Severity, ValidationIssue, ValidationLocation};
+    use crate::{PywrModel, PywrMultiModel};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn test_model_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("models")
+    }
+
+    /// None of the test models should have any validation errors.
+    #[test]
+    fn test_pywr_models_are_valid() {
+        for entry in fs::read_dir(test_model_dir()).unwrap() {
+            let path = entry.unwrap().path();
+            // `extra2.json` is a network included by `extra1.json` rather than a model.
+            if path.extension().is_none_or(|ext| ext != "json") || path.ends_with("extra2.json") {
+                continue;
+            }
+
+            let model = PywrModel::from_path_with_includes(&path).unwrap();
+            let errors: Vec<_> = model
+                .validate()
+                .into_iter()
+                .filter(|issue| issue.severity == Severity::Error)
+                .map(|issue| issue.to_string())
+                .collect();
+
+            assert!(errors.is_empty(), "{}: {errors:?}", path.display());
+        }
+    }
+
+    #[test]
+    fn test_multi_model() {
+        let data = r#"
+        {
+            "metadata": {"title": "Multi-model"},
+            "timestepper": {"start": "2015-01-01", "end": "2015-12-31", "timestep": 1},
+            "models": [
+                {"name": "a", "path": "a.json"},
+                {"name": "b"},
+                {"name": "a", "filename": "c.json"}
+            ]
+        }
+        "#;
+
+        let multi_model: PywrMultiModel = serde_json::from_str(data).unwrap();
+        let issues: Vec<_> = multi_model
+            .validate()
+            .iter()
+            .map(|issue| issue.to_string())
+            .collect();
+
+        assert_eq!(
+            issues,
+            vec![
+                "error: sub-model `b`: has no `data`, `filename` or `path`",
+                "error: sub-model `a`: duplicate sub-model name",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_timestepper() {
+        let data = r#"
+        {
+            "metadata": {"title": "Timestepper"},
+            "timestepper": {"start": "2016-01-01", "end": "2015-12-31", "timestep": "Q"},
+            "nodes": []
+        }
+        "#;
+
+        let model: PywrModel = serde_json::from_str(data).unwrap();
+        let issues: Vec<_> = model.validate().iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            issues,
+            vec![
+                "error: timestepper: invalid timestep frequency `Q`",
+                "error: timestepper: starts (2016-01-01) after it ends (2015-12-31)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scenarios() {
+        let data = r#"
+        {
+            "metadata": {"title": "Scenarios"},
+            "timestepper": {"start": "2015-01-01", "end": "2015-12-31", "timestep": 1},
+            "scenarios": [
+                {"name": "a", "size": 4, "slice": [5, null]},
+                {"name": "b", "size": 2, "slice": [null, null, 0]},
+                {"name": "c", "size": 0}
+            ],
+            "nodes": []
+        }
+        "#;
+
+        let mut model: PywrModel = serde_json::from_str(data).unwrap();
+        let issues: Vec<_> = model.validate().iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            issues,
+            vec![
+                "error: scenario `a`: slice selects none of its 4 indices",
+                "error: scenario `b`: slice step cannot be zero",
+                "error: scenario `c`: has a size of zero",
+            ]
+        );
+
+        model.scenarios.as_mut().unwrap().pop();
+        model.scenario_combinations = Some(vec![vec![0, 1], vec![0, 2], vec![0], vec![0, 1]]);
+        let issues: Vec<_> = model.validate().iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            issues,
+            vec![
+                "warning: scenario `a`: slice is ignored because `scenario_combinations` are given",
+                "warning: scenario `b`: slice is ignored because `scenario_combinations` are given",
+                "error: scenario combination 1: index 2 is out of range for scenario `b` of size 2",
+                "error: scenario combination 2: has 1 indices, but there are 2 scenarios",
+                "warning: scenario combination 3: duplicates scenario combination 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scenario_references() {
+        let data = r#"
+        {
+            "metadata": {"title": "Scenario references"},
+            "timestepper": {"start": "2015-01-01", "end": "2015-12-31", "timestep": 1},
+            "scenarios": [
+                {"name": "a", "size": 2, "ensemble_names": ["First", "Second", "Third"]},
+                {"name": "a", "size": 2}
+            ],
+            "nodes": [
+                {
+                    "name": "supply1",
+                    "type": "Input",
+                    "max_flow": {"type": "constantscenario", "scenario": "b", "values": [1, 2]}
+                },
+                {
+                    "name": "demand1",
+                    "type": "Output",
+                    "max_flow": {
+                        "type": "max",
+                        "parameter": {"type": "constantscenario", "scenario": "a", "values": [1]}
+                    }
+                }
+            ],
+            "edges": [["supply1", "demand1"]],
+            "parameters": {
+                "wrapper": {"type": "scenariowrapper", "scenario": "a", "parameters": [1, 2, 3]},
+                "flow": {"type": "dataframe", "scenario": "c", "url": "flow.csv"},
+                "constant": {"type": "constantscenario", "scenario": "a", "values": [1, 2]}
+            }
+        }
+        "#;
+
+        let model: PywrModel = serde_json::from_str(data).unwrap();
+        let issues: Vec<_> = model.validate().iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            issues,
+            vec![
+                "error: scenario `a`: has 3 ensemble names, but a size of 2",
+                "error: scenario `a`: duplicate scenario name",
+                "error: node `supply1` (attribute `max_flow.scenario`): references missing scenario `b`",
+                "error: node `demand1` (attribute `max_flow.parameter.values`): has 1 values, but scenario `a` has a size of 2",
+                "error: parameter `wrapper` (attribute `parameters`): has 3 parameters, but scenario `a` has a size of 2",
+                "error: parameter `flow` (attribute `scenario`): references missing scenario `c`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unconnected_nodes() {
+        let model = PywrModel::from_path(test_model_dir().join("dangling_link.json")).unwrap();
+        assert_eq!(
+            model.validate(),
+            vec![ValidationIssue::warning(
+                ValidationLocation::Node {
+                    name: "link2".to_string(),
+                    attribute: None,
+                },
+                "has no outgoing edges".to_string(),
+            )]
+        );
+
+        let model = PywrModel::from_path(test_model_dir().join("simple1_broken.json")).unwrap();
+        let issues: Vec<_> = model.validate().iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            issues,
+            vec![
+                "warning: node `link1`: has no outgoing edges",
+                "warning: node `demand1`: has no incoming edges"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_references() {
+        let data = r#"
+            {
+                "metadata": {"title": "Broken references"},
+                "timestepper": {"start": "2015-01-01", "end": "2015-12-31", "timestep": 1},
+                "nodes": [
+                    {"name": "supply1", "type": "Input", "max_flow": "missing_param"},
+                    {"name": "demand1", "type": "Output", "cost": {"table": "missing_table"}},
+                    {"name": "demand1", "type": "Output"},
+                    {"name": "agg1", "type": "AggregatedNode", "nodes": ["supply1", "missing_node"]}
+                ],
+                "edges": [
+                    ["supply1", "demand1"],
+                    ["supply1", "agg1"],
+                    ["supply1", "missing_node"]
+                ],
+                "parameters": {
+                    "p1": {
+                        "type": "aggregated",
+                        "agg_func": "sum",
+                        "parameters": [
+                            "p2",
+                            {"type": "flow", "node": "missing_node"}
+                        ]
+                    },
+                    "p3": {"type": "max", "parameter": "p3"}
+                },
+                "recorders": {
+                    "r1": {"type": "NumpyArrayParameterRecorder", "parameter": "p2"},
+                    "r2": {"type": "aggregated", "recorders": ["r1", "r3"]}
+                }
+            }
+            "#;
+
+        let model: PywrModel = serde_json::from_str(data).unwrap();
+        let issues: Vec<_> = model.validate().iter().map(|i| i.to_string()).collect();
+
+        assert_eq!(
+            issues,
+            vec![
+                "error: node `demand1`: duplicate node name",
+                "error: edge 1 (`supply1` -> `agg1`): connects virtual node `agg1`",
+                "error: edge 2 (`supply1` -> `missing_node`): references missing node `missing_node`",
+                "error: node `supply1` (attribute `max_flow`): references missing parameter `missing_param`",
+                "error: node `demand1` (attribute `cost`): references missing table `missing_table`",
+                "error: node `agg1` (attribute `nodes`): references missing node `missing_node`",
+                "error: parameter `p1` (attribute `parameters[0]`): references missing parameter `p2`",
+                "error: parameter `p1` (attribute `parameters[1].node`): references missing node `missing_node`",
+                "error: recorder `r1` (attribute `parameter`): references missing parameter `p2`",
+                "error: recorder `r2` (attribute `recorders`): references missing recorder `r3`",
+                "error: parameter `p3`: circular parameter reference: p3 -> p3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_sub_node_references() {
+        let data = r#"
+            {
+                "metadata": {"title": "Sub-node references"},
+                "timestepper": {"start": "2015-01-01", "end": "2015-12-31", "timestep": 1},
+                "nodes": [
+                    {"name": "reservoir1", "type": "Storage", "max_volume": 100, "initial_volume": 50},
+                    {"name": "link1", "type": "LossLink", "loss_factor": 0.1},
+                    {"name": "demand1", "type": "Output"},
+                    {
+                        "name": "agg1",
+                        "type": "AggregatedNode",
+                        "nodes": ["link1 Gross", "link1 Net", "link1 bogus", "reservoir1 bogus"]
+                    }
+                ],
+                "edges": [["reservoir1", "link1"], ["link1", "demand1"]]
+            }
+            "#;
+
+        let model: PywrModel = serde_json::from_str(data).unwrap();
+        let issues: Vec<_> = model.validate().iter().map(|i| i.to_string()).collect();
+
+        assert_eq!(
+            issues,
+            vec![
+                "error: node `agg1` (attribute `nodes`): references missing node `link1 bogus`",
+                "error: node `agg1` (attribute `nodes`): references missing node `reservoir1 bogus`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_optional_list_references() {
+        // The `max_flows` of a piecewise link may contain `null` entries.
+        let data = r#"
+            {
+                "metadata": {"title": "Broken piecewise link"},
+                "timestepper": {"start": "2015-01-01", "end": "2015-12-31", "timestep": 1},
+                "nodes": [
+                    {"name": "supply1", "type": "Input"},
+                    {
+                        "name": "link1",
+                        "type": "piecewiselink",
+                        "nsteps": 2,
+                        "costs": [-10.0, 5.0],
+                        "max_flows": [null, "missing_param"]
+                    },
+                    {"name": "demand1", "type": "Output"}
+                ],
+                "edges": [["supply1", "link1"], ["link1", "demand1"]]
+            }
+            "#;
+
+        let model: PywrModel = serde_json::from_str(data).unwrap();
+        let issues: Vec<_> = model.validate().iter().map(|i| i.to_string()).collect();
+
+        assert_eq!(
+            issues,
+            vec![
+                "error: node `link1` (attribute `max_flows[1]`): references missing parameter `missing_param`"
+            ]
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use crate::report::{
+    EXIT_PARSE_FAILURE, EXIT_SUCCESS, EXIT_VALIDATION_ERRORS, EXIT_VALIDATION_WARNINGS, Options,
+    Report, worst_exit_code,
+};
+use glob::Pattern;
+use pywr_v1_schema::validation::Severity;
+use rayon::prelude::*;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Return the JSON files under `dir`, in order of their path.
+///
+/// A file is included if its path relative to `dir` matches any of the `include` globs (or
+/// it has a `.json` extension if there are none), and does not match any of the `exclude`
+/// globs.
+pub fn find_files(
+    dir: &Path,
+    include: &[Pattern],
+    exclude: &[Pattern],
+) -> Result<Vec<PathBuf>, walkdir::Error> {
+    let mut files = Vec::new();
+
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let relative = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+        let included = match include.is_empty() {
+            true => relative.extension().is_some_and(|ext| ext == "json"),
+            false => include.iter().any(|p| p.matches_path(relative)),
+        };
+
+        if included && !exclude.iter().any(|p| p.matches_path(relative)) {
+            files.push(entry.into_path());
+        }
+    }
+
+    Ok(files)
+}
+
+/// The reports of validating a collection of files.
+#[derive(Serialize)]
+pub struct BatchReport {
+    pub files: Vec<Report>,
+    /// The files that were not validated because they are included by other files. These
+    /// are networks rather than models.
+    pub included: Vec<PathBuf>,
+    /// The number of files that use each custom node type.
+    pub custom_node_types: BTreeMap<String, usize>,
+    /// The number of files that use each custom parameter type.
+    pub custom_parameter_types: BTreeMap<String, usize>,
+}
+
+impl BatchReport {
+    /// Validate each of `files` in parallel, except those included by another of the files.
+    pub fn new(files: &[PathBuf], options: &Options) -> Self {
+        let included_paths = included_files(files);
+        let (included, files): (Vec<_>, Vec<_>) = files.iter().partition(|path| {
+            path.canonicalize()
+                .is_ok_and(|path| included_paths.contains(&path))
+        });
+
+        let files: Vec<_> = files
+            .par_iter()
+            .map(|path| Report::new(path, options))
+            .collect();
+
+        let mut custom_node_types = BTreeMap::new();
+        let mut custom_parameter_types = BTreeMap::new();
+        for report in &files {
+            // Count each type once per file, even if it is used by several sub-models.
+            let node_types: BTreeSet<_> = report
+                .reports()
+                .flat_map(|r| &r.custom_node_types)
+                .collect();
+            for ty in node_types {
+                *custom_node_types.entry(ty.clone()).or_default() += 1;
+            }
+
+            let parameter_types: BTreeSet<_> = report
+                .reports()
+                .flat_map(|r| &r.custom_parameter_types)
+                .collect();
+            for ty in parameter_types {
+                *custom_parameter_types.entry(ty.clone()).or_default() += 1;
+            }
+        }
+
+        Self {
+            files,
+            included: included.into_iter().cloned().collect(),
+            custom_node_types,
+            custom_parameter_types,
+        }
+    }
+
+    /// The exit code of the validator for the worst of the files.
+    pub fn exit_code(&self) -> u8 {
+        worst_exit_code(self.files.iter().map(Report::exit_code))
+    }
+
+    /// Print a table summarising the report of each file, with paths relative to `dir`,
+    /// followed by the custom types used across all of the files.
+    pub fn print(&self, dir: &Path) {
+        let header = [
+            "File",
+            "Status",
+            "Errors",
+            "Warnings",
+            "Custom nodes",
+            "Custom parameters",
+        ]
+        .map(String::from);
+
+        let rows: Vec<[String; 6]> = self
+            .files
+            .iter()
+            .map(|report| {
+                let issues: Vec<_> = report.reports().flat_map(|r| &r.issues).collect();
+                let issue_errors = issues
+                    .iter()
+                    .filter(|i| i.severity == Severity::Error)
+                    .count();
+                let load_errors: usize = report.reports().map(|r| r.errors.len()).sum();
+                let mismatches: usize =
+                    report.reports().map(|r| r.core_type_mismatches.len()).sum();
+                let errors = issue_errors + load_errors;
+                let warnings = issues.len() - issue_errors + mismatches;
+                let custom_types = |types: fn(&Report) -> &Vec<String>| {
+                    report
+                        .reports()
+                        .flat_map(types)
+                        .collect::<BTreeSet<_>>()
+                        .len()
+                };
+
+                [
+                    report
+                        .path
+                        .strip_prefix(dir)
+                        .unwrap_or(&report.path)
+                        .display()
+                        .to_string(),
+                    status(report.exit_code()).to_string(),
+                    errors.to_string(),
+                    warnings.to_string(),
+                    custom_types(|r| &r.custom_node_types).to_string(),
+                    custom_types(|r| &r.custom_parameter_types).to_string(),
+                ]
+            })
+            .collect();
+
+        let mut widths = header.clone().map(|h| h.len());
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let print_row = |row: &[String; 6]| {
+            let cells: Vec<_> = row
+                .iter()
+                .zip(widths)
+                .enumerate()
+                .map(|(i, (cell, width))| match i {
+                    // Left align the file and status, and right align the counts.
+                    0 | 1 => format!("{cell:<width$}"),
+                    _ => format!("{cell:>width$}"),
+                })
+                .collect();
+            println!("{}", cells.join("  ").trim_end());
+        };
+
+        print_row(&header);
+        println!("{}", widths.map(|w| "-".repeat(w)).join("  "));
+        for row in &rows {
+            print_row(row);
+        }
+
+        let count = |code| self.files.iter().filter(|r| r.exit_code() == code).count();
+        println!();
+        println!(
+            "Validated {} files: {} ok, {} with warnings, {} with errors, {} failed to parse",
+            self.files.len(),
+            count(EXIT_SUCCESS),
+            count(EXIT_VALIDATION_WARNINGS),
+            count(EXIT_VALIDATION_ERRORS),
+            count(EXIT_PARSE_FAILURE),
+        );
+        if !self.included.is_empty() {
+            let plural = if self.included.len() == 1 { "" } else { "s" };
+            println!(
+                "Skipped {} file{plural} included by other files",
+                self.included.len()
+            );
+        }
+
+        for (component, types) in [
+            ("node", &self.custom_node_types),
+            ("parameter", &self.custom_parameter_types),
+        ] {
+            if types.is_empty() {
+                println!("No custom {component}s found!");
+                continue;
+            }
+
+            println!("Found {} custom {component} types:", types.len());
+            for (ty, files) in types {
+                let plural = if *files == 1 { "" } else { "s" };
+                println!("  {ty} ({files} file{plural})");
+            }
+        }
+    }
+}
+
+/// The canonical paths of the JSON files listed in the `includes` of any of `files`.
+fn included_files(files: &[PathBuf]) -> HashSet<PathBuf> {
+    let mut included = HashSet::new();
+
+    for path in files {
+        // Any errors are reported when the file is validated.
+        let Some(value) = fs::read_to_string(path)
+            .ok()
+            .and_then(|source| serde_json::from_str::<Value>(&source).ok())
+        else {
+            continue;
+        };
+
+        let parent = path.parent().unwrap_or_else(|| Path::new(""));
+        let includes = value.get("includes").and_then(Value::as_array);
+        for include in includes.into_iter().flatten().filter_map(Value::as_str) {
+            let include = Path::new(include);
+            if include.extension().is_some_and(|ext| ext == "json")
+                && let Ok(include) = parent.join(include).canonicalize()
+            {
+                included.insert(include);
+            }
+        }
+    }
+
+    included
+}
+
+/// A short description of the result of validating a file from its exit code.
+fn status(exit_code: u8) -> &'static str {
+    match exit_code {
+        EXIT_SUCCESS => "ok",
+        EXIT_VALIDATION_WARNINGS => "warnings",
+        EXIT_VALIDATION_ERRORS => "errors",
+        _ => "failed to parse",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BatchReport, find_files};
+    use crate::report::{EXIT_VALIDATION_WARNINGS, Options};
+    use glob::Pattern;
+    use std::path::PathBuf;
+
+    fn test_model_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../pywr-v1-schema/tests/models")
+    }
+
+    #[test]
+    fn test_find_files() {
+        let dir = test_model_dir();
+        let all = find_files(&dir, &[], &[]).unwrap();
+        assert!(all.iter().all(|p| p.extension().unwrap() == "json"));
+        assert!(all.is_sorted());
+
+        let include = [Pattern::new("reservoir*.json").unwrap()];
+        let exclude = [Pattern::new("*_cc*").unwrap()];
+        let files = find_files(&dir, &include, &exclude).unwrap();
+
+        assert!(!files.is_empty());
+        assert!(files.len() < all.len());
+        for file in &files {
+            let name = file.file_name().unwrap().to_str().unwrap();
+            assert!(name.starts_with("reservoir") && !name.contains("_cc"));
+        }
+    }
+
+    #[test]
+    fn test_batch_report() {
+        let dir = test_model_dir();
+        let files = find_files(&dir, &[], &[]).unwrap();
+        let report = BatchReport::new(&files, &Options::default());
+
+        // `extra2.json` is a network included by `extra1.json`, rather than a model.
+        assert_eq!(report.included, vec![dir.join("extra2.json")]);
+        assert_eq!(report.files.len(), files.len() - 1);
+        assert!(report.files.iter().all(|r| r.parsed));
+        // Some of the test models have warnings, but none have errors.
+        assert_eq!(report.exit_code(), EXIT_VALIDATION_WARNINGS);
+        assert_eq!(
+            report.custom_parameter_types.get("interpolatedvolume"),
+            Some(&1)
+        );
+    }
+}
@@ -1,88 +1,226 @@
-use clap::Parser;
-use std::collections::HashSet;
+mod batch;
+mod report;
+mod sarif;
+
+use batch::{BatchReport, find_files};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use glob::Pattern;
+use pywr_v1_schema::PywrModel;
+use report::{EXIT_PARSE_FAILURE, EXIT_SUCCESS, LoadError, Options, Report, print_load_error};
+use sarif::sarif_log;
+use serde::Serialize;
 use std::fs::File;
-use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
 /// Simple program to greet a person
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = None,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true,
+    after_help = "Exit codes:
+  0  The file is valid.
+  1  The file could not be parsed, or bundled.
+  2  The command line arguments are invalid.
+  3  Validation found errors.
+  4  Validation found warnings, but no errors."
+)]
 struct Args {
-    #[arg(short, long)]
-    path: std::path::PathBuf,
-    #[arg(short, long)]
-    network_only: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[arg(short, long, required = true)]
+    path: Option<PathBuf>,
+    #[command(flatten)]
+    options: Options,
+    /// The format of the validation report.
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Format {
+    /// Human-readable text.
+    Text,
+    /// A JSON report of the parse status, custom types, validation issues and resources.
+    Json,
+    /// A SARIF 2.1.0 log, for use with code scanning tools.
+    Sarif,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the differences between two Pywr model files.
+    Diff { old: PathBuf, new: PathBuf },
+    /// Validate every JSON model under a directory, in parallel.
+    Batch {
+        dir: PathBuf,
+        /// Only validate files whose path relative to the directory matches one of these
+        /// globs. By default, all files with a `.json` extension are validated.
+        #[arg(short, long, value_parser = parse_glob)]
+        include: Vec<Pattern>,
+        /// Skip files whose path relative to the directory matches one of these globs.
+        #[arg(short, long, value_parser = parse_glob)]
+        exclude: Vec<Pattern>,
+        #[command(flatten)]
+        options: Options,
+        /// The format of the validation report.
+        #[arg(short, long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Copy a model and its local resources into a directory, or a tar archive if the output
+    /// has a `.tar` extension, that loads without the original files.
+    Bundle { path: PathBuf, output: PathBuf },
 }
 
-fn main() {
+fn parse_glob(glob: &str) -> Result<Pattern, glob::PatternError> {
+    Pattern::new(glob)
+}
+
+fn main() -> ExitCode {
     let args = Args::parse();
 
-    println!("Path: {:?}", args.path);
+    let code = match (args.command, args.path) {
+        (Some(Command::Diff { old, new }), _) => diff(&old, &new),
+        (
+            Some(Command::Batch {
+                dir,
+                include,
+                exclude,
+                options,
+                format,
+            }),
+            _,
+        ) => batch(&dir, &include, &exclude, &options, format),
+        (Some(Command::Bundle { path, output }), _) => bundle(&path, &output),
+        (None, Some(path)) => validate(&path, &args.options, args.format),
+        // Clap requires the path when no subcommand is given.
+        (None, None) => Args::command()
+            .error(ErrorKind::MissingRequiredArgument, "--path is required")
+            .exit(),
+    };
+
+    ExitCode::from(code)
+}
 
-    let file = File::open(args.path).expect("Could not open file.");
-    let reader = BufReader::new(file);
+fn diff(old: &Path, new: &Path) -> u8 {
+    let (old, new) = match (load(old), load(new)) {
+        (Ok(old), Ok(new)) => (old, new),
+        (Err(code), _) | (_, Err(code)) => return code,
+    };
 
-    let network: pywr_v1_schema::PywrNetwork = if args.network_only {
-        serde_json::from_reader(reader).expect("Failed to parse Pywr JSON file.")
+    let changes = old.diff(&new);
+    if !changes.is_empty() {
+        println!("Found {} differences:", changes.len());
+        for change in changes {
+            println!("  {}", change.to_string().replace('\n', "\n  "));
+        }
     } else {
-        let model: pywr_v1_schema::PywrModel =
-            serde_json::from_reader(reader).expect("Failed to parse Pywr JSON file.");
+        println!("No differences found!")
+    }
 
-        model.network
-    };
+    EXIT_SUCCESS
+}
 
-    println!("Parsed Pywr JSON file successfully!");
-
-    {
-        // Identify custom nodes
-        let custom_types: HashSet<_> = match network.nodes {
-            Some(nodes) => nodes
-                .iter()
-                .filter_map(|n| {
-                    if let pywr_v1_schema::nodes::Node::Custom(c) = n {
-                        Some(c.ty.clone())
-                    } else {
-                        None
-                    }
-                })
-                .collect(),
-            None => HashSet::new(),
-        };
-
-        let mut custom_types: Vec<_> = custom_types.into_iter().collect();
-        custom_types.sort();
-
-        if !custom_types.is_empty() {
-            println!("Found {} custom node types:", custom_types.len());
-            for ty in custom_types {
-                println!("  {}", ty);
-            }
-        } else {
-            println!("No custom nodes found!")
+fn validate(path: &Path, options: &Options, format: Format) -> u8 {
+    let report = Report::new(path, options);
+
+    match format {
+        Format::Text => report.print(),
+        Format::Json => print_json(&report),
+        Format::Sarif => print_json(&sarif_log(std::slice::from_ref(&report))),
+    }
+
+    report.exit_code()
+}
+
+fn batch(
+    dir: &Path,
+    include: &[Pattern],
+    exclude: &[Pattern],
+    options: &Options,
+    format: Format,
+) -> u8 {
+    let files = match find_files(dir, include, exclude) {
+        Ok(files) => files,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return EXIT_PARSE_FAILURE;
         }
+    };
+
+    let report = BatchReport::new(&files, options);
+
+    match format {
+        Format::Text => report.print(dir),
+        Format::Json => print_json(&report),
+        Format::Sarif => print_json(&sarif_log(&report.files)),
     }
 
-    if let Some(parameters) = network.parameters {
-        let custom_types: HashSet<_> = parameters
-            .iter()
-            .filter_map(|p| {
-                if let pywr_v1_schema::parameters::Parameter::Custom(c) = p {
-                    Some(c.ty.clone())
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        let mut custom_types: Vec<_> = custom_types.into_iter().collect();
-        custom_types.sort();
-
-        if !custom_types.is_empty() {
-            println!("Found {} custom parameter types:", custom_types.len());
-            for ty in custom_types {
-                println!("  {}", ty);
+    report.exit_code()
+}
+
+fn bundle(path: &Path, output: &Path) -> u8 {
+    let model = match load(path) {
+        Ok(model) => model,
+        Err(code) => return code,
+    };
+
+    let result = model.bundle(path).and_then(|bundle| {
+        match output.extension().is_some_and(|ext| ext == "tar") {
+            true => bundle.write_tar(File::create(output)?)?,
+            false => bundle.write_dir(output)?,
+        }
+        Ok(bundle)
+    });
+
+    match result {
+        Ok(bundle) => {
+            let plural = if bundle.resources.len() == 1 { "" } else { "s" };
+            println!(
+                "Bundled {} with {} resource{plural} into {}",
+                bundle.model_name.display(),
+                bundle.resources.len(),
+                output.display()
+            );
+            for resource in &bundle.resources {
+                println!(
+                    "  {} -> {}",
+                    resource.source.display(),
+                    resource.path.display()
+                );
             }
-        } else {
-            println!("No custom parameters found!")
+            EXIT_SUCCESS
         }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            EXIT_PARSE_FAILURE
+        }
+    }
+}
+
+/// Print `value` as pretty JSON.
+fn print_json(value: &impl Serialize) {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => println!("{}", json),
+        Err(error) => eprintln!("error: failed to serialize the report: {}", error),
+    }
+}
+
+/// Load a model from `path`, printing every error and returning the exit code if there are
+/// any.
+fn load(path: &Path) -> Result<PywrModel, u8> {
+    let (model, errors) = PywrModel::from_path_recovering(path);
+    for error in &errors {
+        print_load_error(path, &LoadError::from(error));
+    }
+
+    match model {
+        Some(model) if errors.is_empty() => Ok(model),
+        _ => Err(EXIT_PARSE_FAILURE),
     }
 }
@@ -0,0 +1,591 @@
+use pywr_v1_schema::nodes::Node;
+use pywr_v1_schema::parameters::Parameter;
+use pywr_v1_schema::strict::CoreTypeMismatch;
+use pywr_v1_schema::unused::UnusedComponents;
+use pywr_v1_schema::validation::{Severity, ValidationIssue};
+use pywr_v1_schema::{PywrModel, PywrMultiModel, PywrNetwork, PywrSchemaError};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Exit code when the file is valid.
+pub const EXIT_SUCCESS: u8 = 0;
+/// Exit code when the file could not be parsed, including in strict mode.
+pub const EXIT_PARSE_FAILURE: u8 = 1;
+/// Exit code when validation found at least one error.
+pub const EXIT_VALIDATION_ERRORS: u8 = 3;
+/// Exit code when validation found warnings, but no errors.
+pub const EXIT_VALIDATION_WARNINGS: u8 = 4;
+
+/// Options controlling how files are validated.
+#[derive(clap::Args, Clone, Copy, Debug, Default)]
+pub struct Options {
+    /// Load files as networks rather than models.
+    #[arg(short, long)]
+    pub network_only: bool,
+    /// Report the parameters and tables that are not used by the network.
+    #[arg(short, long)]
+    pub unused: bool,
+    /// Fail if a node, parameter or recorder has a core type but could not be deserialized
+    /// as that type, rather than treating it as a custom type.
+    #[arg(short, long)]
+    pub strict: bool,
+    /// Check that the external resources of the network exist. Relative paths are resolved
+    /// against the directory of the file.
+    #[arg(short = 'r', long)]
+    pub check_resources: bool,
+}
+
+/// The result of validating a Pywr JSON file.
+///
+/// The report of a multi-model file contains the issues of the file itself, and the report
+/// of each of its sub-models.
+#[derive(Serialize)]
+pub struct Report {
+    pub path: PathBuf,
+    /// The name of the sub-model, if this is the report of a sub-model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Whether the file was parsed successfully. The remaining fields are empty if not.
+    pub parsed: bool,
+    pub strict: bool,
+    /// Whether the file is a multi-model file.
+    pub multi_model: bool,
+    /// The errors that prevented the file from being parsed.
+    pub errors: Vec<LoadError>,
+    /// Components with a core type that were loaded as custom types. In strict mode these
+    /// prevent the file from being parsed.
+    pub core_type_mismatches: Vec<CoreTypeMismatch>,
+    pub custom_node_types: Vec<String>,
+    pub custom_parameter_types: Vec<String>,
+    pub issues: Vec<ValidationIssue>,
+    /// The external files referenced by the network.
+    pub resources: Vec<PathBuf>,
+    /// The resources that are missing or are invalid URLs, if resources were checked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_errors: Option<Vec<String>>,
+    /// The unused parameters and tables, if requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unused: Option<UnusedComponents>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sub_models: Vec<Report>,
+}
+
+/// An error from loading a file.
+#[derive(Serialize)]
+pub struct LoadError {
+    pub message: String,
+    /// The path of the value in the JSON document that caused the error, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// The column of the error on its line, counted in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+impl From<&PywrSchemaError> for LoadError {
+    fn from(error: &PywrSchemaError) -> Self {
+        match error {
+            PywrSchemaError::DeserializationError {
+                message,
+                path,
+                line,
+                column,
+            } => Self {
+                message: message.clone(),
+                json_path: (!path.is_empty()).then(|| path.clone()),
+                line: (*line > 0).then_some(*line),
+                column: (*column > 0).then_some(*column),
+            },
+            error => Self {
+                message: error.to_string(),
+                json_path: None,
+                line: None,
+                column: None,
+            },
+        }
+    }
+}
+
+impl Report {
+    /// Load and validate the model, or network if `network_only` is set, at `path`.
+    ///
+    /// Unless `network_only` is set, files with a `models` attribute are validated as
+    /// multi-model files.
+    pub fn new(path: &Path, options: &Options) -> Self {
+        if options.network_only {
+            let (network, errors) = PywrNetwork::from_path_recovering(path);
+            Self::from_network(path, network, &errors, options)
+        } else {
+            let (model, errors) = PywrModel::from_path_recovering(path);
+            match errors.as_slice() {
+                [PywrSchemaError::MultiModelFile] => Self::from_multi_model(path, options),
+                _ => Self::from_model(path, model, &errors, options),
+            }
+        }
+    }
+
+    /// Validate a multi-model file, and each of its sub-models.
+    fn from_multi_model(path: &Path, options: &Options) -> Self {
+        let multi_model = match PywrMultiModel::from_path(path) {
+            Ok(multi_model) => multi_model,
+            Err(error) => return Self::from_network(path, None, &[error], options),
+        };
+
+        let mut report = Self::empty(path, options);
+        report.parsed = true;
+        report.multi_model = true;
+        report.issues = multi_model.validate();
+
+        for sub_model in &multi_model.models {
+            let mut sub_report = match (&sub_model.data, sub_model.resolve_source_path(path)) {
+                (Some(model), _) => Self::from_model(path, Some(model.clone()), &[], options),
+                (None, Some(sub_model_path)) => {
+                    let (model, errors) = PywrModel::from_path_recovering(&sub_model_path);
+                    Self::from_model(&sub_model_path, model, &errors, options)
+                }
+                // Reported by the validation of the multi-model.
+                (None, None) => continue,
+            };
+
+            sub_report.name = Some(sub_model.name.clone());
+            report.sub_models.push(sub_report);
+        }
+
+        report
+    }
+
+    /// A report of a file that has not been parsed.
+    fn empty(path: &Path, options: &Options) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            name: None,
+            parsed: false,
+            strict: options.strict,
+            multi_model: false,
+            errors: Vec::new(),
+            core_type_mismatches: Vec::new(),
+            custom_node_types: Vec::new(),
+            custom_parameter_types: Vec::new(),
+            issues: Vec::new(),
+            resources: Vec::new(),
+            resource_errors: None,
+            unused: None,
+            sub_models: Vec::new(),
+        }
+    }
+
+    /// Validate `model`, which was loaded from `path` with `errors`, including its
+    /// timestepper and scenarios.
+    fn from_model(
+        path: &Path,
+        model: Option<PywrModel>,
+        errors: &[PywrSchemaError],
+        options: &Options,
+    ) -> Self {
+        let issues = model.as_ref().map(PywrModel::validate).unwrap_or_default();
+        Self::from_loaded(path, model.map(|m| m.network), issues, errors, options)
+    }
+
+    /// Validate `network`, which was loaded from `path` with `errors`.
+    fn from_network(
+        path: &Path,
+        network: Option<PywrNetwork>,
+        errors: &[PywrSchemaError],
+        options: &Options,
+    ) -> Self {
+        let issues = network
+            .as_ref()
+            .map(PywrNetwork::validate)
+            .unwrap_or_default();
+        Self::from_loaded(path, network, issues, errors, options)
+    }
+
+    /// The report of `network`, which was loaded from `path` with `errors`, and has the
+    /// validation `issues`.
+    fn from_loaded(
+        path: &Path,
+        network: Option<PywrNetwork>,
+        issues: Vec<ValidationIssue>,
+        errors: &[PywrSchemaError],
+        options: &Options,
+    ) -> Self {
+        let mut report = Self::empty(path, options);
+        report.errors = errors.iter().map(LoadError::from).collect();
+
+        let Some(network) = network.filter(|_| errors.is_empty()) else {
+            return report;
+        };
+
+        report.core_type_mismatches = network.core_type_mismatches();
+        if options.strict && !report.core_type_mismatches.is_empty() {
+            return report;
+        }
+
+        report.parsed = true;
+        report.issues = issues;
+        report.unused = options.unused.then(|| network.unused_components());
+
+        report.custom_node_types = network
+            .nodes
+            .iter()
+            .flatten()
+            .filter_map(|n| match n {
+                Node::Custom(c) => Some(c.ty.clone()),
+                Node::Core(_) => None,
+            })
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        report.custom_parameter_types = network
+            .parameters
+            .iter()
+            .flat_map(|p| p.iter())
+            .filter_map(|p| match p {
+                Parameter::Custom(c) => Some(c.ty.clone()),
+                Parameter::Core(_) => None,
+            })
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        report.resources = network.resource_paths().into_iter().collect();
+        report.resources.sort();
+        report.resource_errors = options.check_resources.then(|| {
+            let errors = network.check_resources(path);
+            errors.iter().map(|e| e.to_string()).collect()
+        });
+
+        report
+    }
+
+    /// This report followed by the reports of any sub-models.
+    pub fn reports(&self) -> impl Iterator<Item = &Report> {
+        std::iter::once(self).chain(&self.sub_models)
+    }
+
+    /// The exit code of the validator for this report, including any sub-models.
+    pub fn exit_code(&self) -> u8 {
+        let code = if !self.parsed {
+            EXIT_PARSE_FAILURE
+        } else if self.issues.iter().any(|i| i.severity == Severity::Error)
+            || self.resource_errors.as_ref().is_some_and(|e| !e.is_empty())
+        {
+            EXIT_VALIDATION_ERRORS
+        } else if !self.issues.is_empty() || !self.core_type_mismatches.is_empty() {
+            EXIT_VALIDATION_WARNINGS
+        } else {
+            EXIT_SUCCESS
+        };
+
+        worst_exit_code(std::iter::once(code).chain(self.sub_models.iter().map(Self::exit_code)))
+    }
+
+    /// Print the report as text. Errors that prevented the file from being parsed are
+    /// printed to stderr.
+    pub fn print(&self) {
+        println!("Path: {:?}", self.path);
+
+        if !self.errors.is_empty() {
+            for error in &self.errors {
+                print_load_error(&self.path, error);
+            }
+            if self.errors.len() > 1 {
+                eprintln!(
+                    "Found {} errors in {}",
+                    self.errors.len(),
+                    self.path.display()
+                );
+            }
+            return;
+        }
+
+        if !self.parsed {
+            println!("Failed to parse Pywr JSON file in strict mode:");
+            for mismatch in &self.core_type_mismatches {
+                println!("  {}", mismatch);
+            }
+            return;
+        }
+
+        if self.multi_model {
+            self.print_multi_model();
+            return;
+        }
+
+        println!("Parsed Pywr JSON file successfully!");
+
+        if !self.core_type_mismatches.is_empty() {
+            println!(
+                "Found {} components that were loaded as custom types:",
+                self.core_type_mismatches.len()
+            );
+            for mismatch in &self.core_type_mismatches {
+                println!("  warning: {}", mismatch);
+            }
+        }
+
+        if !self.issues.is_empty() {
+            println!("Found {} validation issues:", self.issues.len());
+            for issue in &self.issues {
+                println!("  {}", issue);
+            }
+        } else {
+            println!("No validation issues found!")
+        }
+
+        if let Some(unused) = &self.unused {
+            if !unused.is_empty() {
+                if !unused.parameters.is_empty() {
+                    println!("Found {} unused parameters:", unused.parameters.len());
+                    for name in &unused.parameters {
+                        println!("  {}", name);
+                    }
+                }
+                if !unused.tables.is_empty() {
+                    println!("Found {} unused tables:", unused.tables.len());
+                    for name in &unused.tables {
+                        println!("  {}", name);
+                    }
+                }
+            } else {
+                println!("No unused parameters or tables found!")
+            }
+        }
+
+        if !self.custom_node_types.is_empty() {
+            println!("Found {} custom node types:", self.custom_node_types.len());
+            for ty in &self.custom_node_types {
+                println!("  {}", ty);
+            }
+        } else {
+            println!("No custom nodes found!")
+        }
+
+        if !self.custom_parameter_types.is_empty() {
+            println!(
+                "Found {} custom parameter types:",
+                self.custom_parameter_types.len()
+            );
+            for ty in &self.custom_parameter_types {
+                println!("  {}", ty);
+            }
+        } else {
+            println!("No custom parameters found!")
+        }
+
+        if !self.resources.is_empty() {
+            println!("Found {} external resources:", self.resources.len());
+            for resource in &self.resources {
+                println!("  {}", resource.display());
+            }
+        }
+
+        if let Some(resource_errors) = &self.resource_errors {
+            if !resource_errors.is_empty() {
+                println!(
+                    "Found {} missing or invalid resources:",
+                    resource_errors.len()
+                );
+                for error in resource_errors {
+                    println!("  {}", error);
+                }
+            } else {
+                println!("All local resources found!")
+            }
+        }
+    }
+
+    fn print_multi_model(&self) {
+        println!(
+            "Parsed multi-model file with {} sub-models successfully!",
+            self.sub_models.len()
+        );
+
+        if !self.issues.is_empty() {
+            println!("Found {} validation issues:", self.issues.len());
+            for issue in &self.issues {
+                println!("  {}", issue);
+            }
+        }
+
+        for sub_model in &self.sub_models {
+            println!();
+            println!(
+                "Sub-model `{}`:",
+                sub_model.name.as_deref().unwrap_or_default()
+            );
+            sub_model.print();
+        }
+    }
+}
+
+/// Return the most severe of the exit codes of the validator in `codes`.
+pub fn worst_exit_code(codes: impl IntoIterator<Item = u8>) -> u8 {
+    let codes: Vec<_> = codes.into_iter().collect();
+
+    [
+        EXIT_PARSE_FAILURE,
+        EXIT_VALIDATION_ERRORS,
+        EXIT_VALIDATION_WARNINGS,
+    ]
+    .into_iter()
+    .find(|code| codes.contains(code))
+    .unwrap_or(EXIT_SUCCESS)
+}
+
+/// Print an error from loading the file at `path`. Errors with a position are printed in
+/// the style of a compiler diagnostic, with the line of the file that caused them.
+pub fn print_load_error(path: &Path, error: &LoadError) {
+    eprintln!("error: {}", error.message);
+
+    let (Some(line), Some(column)) = (error.line, error.column) else {
+        return;
+    };
+
+    let source = fs::read_to_string(path).unwrap_or_default();
+    let source_line = source.lines().nth(line - 1);
+    let column = source_line.map_or(column, |l| char_column(l, column));
+
+    let width = line.to_string().len();
+    eprintln!("{:width$}--> {}:{}:{}", "", path.display(), line, column);
+
+    if let Some(source_line) = source_line {
+        // Keep any tabs before the column so that the marker lines up with the source.
+        let indent: String = source_line
+            .chars()
+            .take(column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        eprintln!("{:width$} |", "");
+        eprintln!("{} | {}", line, source_line);
+        eprintln!("{:width$} | {}^", "", indent);
+    }
+
+    if let Some(json_path) = &error.json_path {
+        eprintln!("{:width$} = note: in `{}`", "", json_path);
+    }
+}
+
+/// Return the column, counted in characters, of the byte `column` of `line`. Both columns
+/// start at 1.
+pub(crate) fn char_column(line: &str, column: usize) -> usize {
+    line.char_indices()
+        .take_while(|(i, _)| *i < column - 1)
+        .count()
+        + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        EXIT_PARSE_FAILURE, EXIT_SUCCESS, EXIT_VALIDATION_ERRORS, EXIT_VALIDATION_WARNINGS,
+        Options, Report, char_column,
+    };
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn test_model(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../pywr-v1-schema/tests/models")
+            .join(name)
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let report = Report::new(&test_model("simple1.json"), &Options::default());
+        assert_eq!(report.exit_code(), EXIT_SUCCESS);
+
+        let report = Report::new(&test_model("dangling_link.json"), &Options::default());
+        assert_eq!(report.exit_code(), EXIT_VALIDATION_WARNINGS);
+
+        // Model level issues, such as this duplicate scenario combination, are also reported.
+        let path = test_model("timeseries3_subsample.json");
+        let report = Report::new(&path, &Options::default());
+        assert_eq!(report.exit_code(), EXIT_VALIDATION_WARNINGS);
+
+        let path = test_model("reservoir_evaporation_areafromfile.json");
+        let report = Report::new(&path, &Options::default());
+        assert_eq!(report.exit_code(), EXIT_VALIDATION_WARNINGS);
+        let options = Options {
+            strict: true,
+            ..Default::default()
+        };
+        let report = Report::new(&path, &options);
+        assert_eq!(report.exit_code(), EXIT_PARSE_FAILURE);
+
+        let report = Report::new(&test_model("missing.json"), &Options::default());
+        assert_eq!(report.exit_code(), EXIT_PARSE_FAILURE);
+        assert_eq!(report.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_multi_model() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../pywr-v1-schema/tests/multi_models/two_models.json");
+        let report = Report::new(&path, &Options::default());
+
+        assert!(report.multi_model);
+        let names: Vec<_> = report
+            .sub_models
+            .iter()
+            .map(|r| r.name.as_deref().unwrap())
+            .collect();
+        assert_eq!(names, vec!["simple", "dangling"]);
+        assert!(report.sub_models.iter().all(|r| r.parsed));
+        // The warning of the `dangling` sub-model is the worst result.
+        assert_eq!(report.exit_code(), EXIT_VALIDATION_WARNINGS);
+    }
+
+    #[test]
+    fn test_check_resources() {
+        let options = Options {
+            check_resources: true,
+            ..Default::default()
+        };
+        // The CSV file referenced by this model is not part of the test data.
+        let report = Report::new(&test_model("timeseries1.json"), &options);
+
+        assert_eq!(report.resource_errors.as_ref().unwrap().len(), 1);
+        assert_eq!(report.exit_code(), EXIT_VALIDATION_ERRORS);
+
+        let report = Report::new(&test_model("simple1.json"), &options);
+        assert_eq!(report.resource_errors, Some(vec![]));
+        assert_eq!(report.exit_code(), EXIT_SUCCESS);
+    }
+
+    #[test]
+    fn test_json_report() {
+        let options = Options {
+            unused: true,
+            ..Default::default()
+        };
+        let report = Report::new(&test_model("dangling_link.json"), &options);
+        let value = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(value["parsed"], json!(true));
+        assert_eq!(value["errors"], json!([]));
+        assert_eq!(
+            value["issues"],
+            json!([{
+                "severity": "warning",
+                "location": {"component": "node", "name": "link2", "attribute": null},
+                "message": "has no outgoing edges"
+            }])
+        );
+        assert_eq!(value["unused"], json!({"parameters": [], "tables": []}));
+    }
+
+    #[test]
+    fn test_char_column() {
+        assert_eq!(char_column(r#"{"a": 1}"#, 7), 7);
+        // `é` is two bytes long.
+        assert_eq!(char_column(r#"{"é": 1}"#, 8), 7);
+        assert_eq!(char_column("", 1), 1);
+    }
+}
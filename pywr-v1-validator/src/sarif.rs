@@ -0,0 +1,156 @@
+use crate::report::{Report, char_column};
+use serde_json::{Value, json};
+use std::fs;
+
+/// Return a [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+/// log of `reports`, for use with code scanning tools. Each report, and the report of each
+/// sub-model, is given as a run.
+pub fn sarif_log(reports: &[Report]) -> Value {
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": reports.iter().flat_map(Report::reports).map(sarif_run).collect::<Vec<_>>()
+    })
+}
+
+/// Return the SARIF run of `report`.
+///
+/// Parse errors, components loaded as custom types and validation issues are given as
+/// results. The rest of the report is given in the properties of the run.
+fn sarif_run(report: &Report) -> Value {
+    let uri = report.path.to_string_lossy().replace('\\', "/");
+    let mut results = Vec::new();
+
+    let source = (!report.errors.is_empty())
+        .then(|| fs::read_to_string(&report.path).ok())
+        .flatten()
+        .unwrap_or_default();
+
+    for error in &report.errors {
+        let mut location = json!({
+            "physicalLocation": {"artifactLocation": {"uri": uri}}
+        });
+        if let (Some(line), Some(column)) = (error.line, error.column) {
+            // Load errors give the column in bytes.
+            let column = source
+                .lines()
+                .nth(line - 1)
+                .map_or(column, |l| char_column(l, column));
+            location["physicalLocation"]["region"] =
+                json!({"startLine": line, "startColumn": column});
+        }
+        if let Some(json_path) = &error.json_path {
+            location["logicalLocations"] = json!([{"fullyQualifiedName": json_path}]);
+        }
+
+        results.push(json!({
+            "ruleId": "parse-error",
+            "level": "error",
+            "message": {"text": error.message},
+            "locations": [location]
+        }));
+    }
+
+    for mismatch in &report.core_type_mismatches {
+        results.push(json!({
+            "ruleId": "core-type-mismatch",
+            "level": if report.strict { "error" } else { "warning" },
+            "message": {"text": mismatch.to_string()},
+            "locations": [component_location(&uri, &mismatch.location.to_string())]
+        }));
+    }
+
+    for error in report.resource_errors.iter().flatten() {
+        results.push(json!({
+            "ruleId": "resource",
+            "level": "error",
+            "message": {"text": error},
+            "locations": [{"physicalLocation": {"artifactLocation": {"uri": uri}}}]
+        }));
+    }
+
+    for issue in &report.issues {
+        results.push(json!({
+            "ruleId": "validation",
+            // SARIF uses the same names for these levels.
+            "level": issue.severity.to_string(),
+            "message": {"text": issue.message},
+            "locations": [component_location(&uri, &issue.location.to_string())]
+        }));
+    }
+
+    json!({
+        "tool": {
+            "driver": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+                "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                "rules": [
+                    {
+                        "id": "parse-error",
+                        "shortDescription": {"text": "The file could not be parsed."}
+                    },
+                    {
+                        "id": "core-type-mismatch",
+                        "shortDescription": {
+                            "text": "A component with a core type was loaded as a custom type."
+                        }
+                    },
+                    {
+                        "id": "validation",
+                        "shortDescription": {"text": "The network failed validation."}
+                    },
+                    {
+                        "id": "resource",
+                        "shortDescription": {
+                            "text": "An external resource is missing or is an invalid URL."
+                        }
+                    }
+                ]
+            }
+        },
+        "artifacts": [{"location": {"uri": uri}}],
+        "results": results,
+        "properties": {
+            "subModel": report.name,
+            "parsed": report.parsed,
+            "customNodeTypes": report.custom_node_types,
+            "customParameterTypes": report.custom_parameter_types,
+            "resources": report.resources,
+            "unused": report.unused
+        }
+    })
+}
+
+/// A SARIF location of the component `name` within the file at `uri`.
+fn component_location(uri: &str, name: &str) -> Value {
+    json!({
+        "physicalLocation": {"artifactLocation": {"uri": uri}},
+        "logicalLocations": [{"fullyQualifiedName": name}]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sarif_log;
+    use crate::report::{Options, Report};
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_sarif_log() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../pywr-v1-schema/tests/models/dangling_link.json");
+        let log = sarif_log(&[Report::new(&path, &Options::default())]);
+
+        assert_eq!(log["version"], json!("2.1.0"));
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["ruleId"], json!("validation"));
+        assert_eq!(results[0]["level"], json!("warning"));
+        assert_eq!(
+            results[0]["locations"][0]["logicalLocations"][0]["fullyQualifiedName"],
+            json!("node `link2`")
+        );
+    }
+}